@@ -1,6 +1,22 @@
 #![allow(deprecated)]
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Burn, MintTo, Transfer};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::Discriminator;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, MintTo, SetAuthority, TokenAccount, TokenInterface, TokenMetadataInitialize,
+    TokenMetadataUpdateAuthority, TokenMetadataUpdateField, Transfer,
+};
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_2022::extension::memo_transfer::MemoTransfer;
+use anchor_spl::token_interface::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::spl_token_2022::state::{Account as TokenAccountState, AccountState, Mint as MintState};
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
 use anchor_spl::associated_token::AssociatedToken;
 
 declare_id!("DdHjSxotiVveS9reai5KvdBFC9xd5HPUeDwPp88LZ98Z");
@@ -30,6 +46,121 @@ pub enum LaunchError {
     #[msg("Below minimum refund threshold")] BelowMinRefund,
     #[msg("Above auto threshold")] AboveAutoThreshold,
     #[msg("Reentrancy detected")] Reentrancy,
+    #[msg("Instruction not allowed in current launch phase")] WrongPhase,
+    #[msg("Invalid launch phase transition")] InvalidTransition,
+    #[msg("Launch not started")] NotStarted,
+    #[msg("Start time out of range")] InvalidStartTime,
+    #[msg("Invalid presale parameters")] InvalidPresale,
+    #[msg("Allowlist proof required")] ProofRequired,
+    #[msg("Invalid allowlist proof")] InvalidProof,
+    #[msg("Presale allocation exceeded")] AllocationExceeded,
+    #[msg("Launch already started")] AlreadyStarted,
+    #[msg("Invalid fixed-price round parameters")] InvalidRound,
+    #[msg("Fixed-price round sold out")] RoundSoldOut,
+    #[msg("Fixed-price round wallet cap exceeded")] RoundWalletCap,
+    #[msg("Fixed-price round met its soft cap")] SoftCapMet,
+    #[msg("Fixed-price round missed its soft cap")] SoftCapMissed,
+    #[msg("Invalid batch auction parameters")] InvalidAuction,
+    #[msg("Bid price tick out of range")] InvalidTick,
+    #[msg("Bid tick cannot change")] TickMismatch,
+    #[msg("Auction window still open")] AuctionOpen,
+    #[msg("Auction window closed")] AuctionClosed,
+    #[msg("Only one opening mode per launch")] ConflictingModes,
+    #[msg("Invalid lottery parameters")] InvalidLottery,
+    #[msg("Lottery registration still open")] RegistrationOpen,
+    #[msg("Lottery draw already committed")] DrawCommitted,
+    #[msg("Lottery draw not committed")] DrawNotCommitted,
+    #[msg("Draw slot not reached")] DrawSlotPending,
+    #[msg("Draw slot hash unavailable, recommit")] DrawSlotExpired,
+    #[msg("Invalid LBP weight schedule")] InvalidWeights,
+    #[msg("Invalid commit-reveal windows")] InvalidCommitReveal,
+    #[msg("Opening window uses commit-reveal")] CommitRevealActive,
+    #[msg("Not in commit window")] NotCommitWindow,
+    #[msg("Not in reveal window")] NotRevealWindow,
+    #[msg("Reveal does not match commitment")] CommitmentMismatch,
+    #[msg("Invalid batch trading parameters")] InvalidBatchTrading,
+    #[msg("Trades go through the batch order book")] BatchTradingOnly,
+    #[msg("Order book full for this batch")] BookFull,
+    #[msg("Batch still collecting orders")] BatchOpen,
+    #[msg("Wrong destination account")] BadDestination,
+    #[msg("Order not found")] OrderNotFound,
+    #[msg("Sandwich guard accounts missing")] GuardAccountsMissing,
+    #[msg("Buy and sell of this launch in one transaction")] SandwichDetected,
+    #[msg("Same-signer round trip within a slot")] RoundTrip,
+    #[msg("Invalid circuit breaker parameters")] InvalidBreaker,
+    #[msg("Circuit breaker active: sells paused")] BreakerHalted,
+    #[msg("Paused by guardian")] Paused,
+    #[msg("Invalid pause scope")] InvalidPauseScope,
+    #[msg("Launch was not wound down")] NotWoundDown,
+    #[msg("Invalid creator bond")] InvalidBond,
+    #[msg("Bond already posted or settled")] BondSettled,
+    #[msg("Creator sells within bond limit")] BondNotSlashable,
+    #[msg("Bond still held")] BondLocked,
+    #[msg("Invalid vesting buckets")] InvalidVesting,
+    #[msg("Invalid lock schedule")] InvalidLock,
+    #[msg("Lock can only be extended")] LockShortened,
+    #[msg("No migration proposed")] NoMigrationProposed,
+    #[msg("Migration timelock active")] MigrationTimelock,
+    #[msg("Invalid LP fee split")] InvalidFeeSplit,
+    #[msg("AMM adapter touched LP principal")] PrincipalMoved,
+    #[msg("AMM adapter not configured")] NoAmmAdapter,
+    #[msg("Liquidity was burned")] LiquidityBurned,
+    #[msg("Signer does not hold the LP position token")] NotPositionHolder,
+    #[msg("Invalid LP unlock schedule")] InvalidUnlockSchedule,
+    #[msg("Invalid token metadata")] InvalidMetadata,
+    #[msg("Metadata is frozen after graduation")] MetadataFrozen,
+    #[msg("Ticker must be 1-10 ASCII letters or digits")] InvalidTicker,
+    #[msg("Ticker already claimed")] TickerTaken,
+    #[msg("Ticker cannot be released")] TickerNotReleasable,
+    #[msg("Sell would trip the circuit breaker")] BreakerLimit,
+    #[msg("LP migration did not complete")] MigrationIncomplete,
+    #[msg("Curve inventory cannot cover owed presale claims")] ClaimsUncovered,
+    #[msg("Registered ticker cannot change")] TickerImmutable,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum LaunchPhase {
+    Scheduled,
+    Presale,
+    Curve,
+    Succeeded,
+    Failed,
+    Graduated,
+    Archived,
+    FixedPriceRound,
+    BatchAuction,
+    Lottery,
+}
+
+impl LaunchPhase {
+    pub fn can_transition_to(self, to: LaunchPhase) -> bool {
+        use LaunchPhase::*;
+        matches!(
+            (self, to),
+            (Scheduled, Presale)
+                | (Scheduled, FixedPriceRound)
+                | (Scheduled, Curve)
+                | (Presale, Curve)
+                | (FixedPriceRound, Curve)
+                | (FixedPriceRound, Failed)
+                | (Scheduled, BatchAuction)
+                | (BatchAuction, Curve)
+                | (Scheduled, Lottery)
+                | (Lottery, Curve)
+                // unsettled auction or undrawn lottery, at the deadline or by wind_down
+                | (BatchAuction, Failed)
+                | (Lottery, Failed)
+                | (Curve, Succeeded)
+                | (Curve, Failed)
+                // guardian wind_down
+                | (Scheduled, Failed)
+                | (Presale, Failed)
+                | (Succeeded, Failed)
+                | (Succeeded, Graduated)
+                | (Failed, Archived)
+                | (Graduated, Archived)
+        )
+    }
 }
 
 #[event]
@@ -79,12 +210,378 @@ pub struct PlatformWithdrawnEvent {
     pub amount: u64,
 }
 
+#[event]
+pub struct LaunchScheduledEvent {
+    pub launch: Pubkey,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub start_time: i64,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct AllowlistUpdatedEvent {
+    pub launch: Pubkey,
+    pub merkle_root: [u8; 32],
+}
+
+#[event]
+pub struct FixedPriceBuyEvent {
+    pub buyer: Pubkey,
+    pub usdc_in: u64,
+    pub tokens: u64,
+}
+
+#[event]
+pub struct FixedPriceRefundEvent {
+    pub buyer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AuctionBidEvent {
+    pub bidder: Pubkey,
+    pub quote_amount: u64,
+    pub max_price: u64,
+}
+
+#[event]
+pub struct AuctionSettledEvent {
+    pub launch: Pubkey,
+    pub clearing_price: u64,
+    pub quote_filled: u64,
+    pub tokens_filled: u64,
+}
+
+#[event]
+pub struct AuctionClaimEvent {
+    pub bidder: Pubkey,
+    pub tokens: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct LotteryCommittedEvent {
+    pub launch: Pubkey,
+    pub draw_slot: u64,
+    pub entries: u32,
+}
+
+#[event]
+pub struct LotteryDrawnEvent {
+    pub launch: Pubkey,
+    pub seed: [u8; 32],
+    pub entries: u32,
+    pub winners: u32,
+    pub tokens_per_ticket: u64,
+}
+
+#[event]
+pub struct LotteryClaimEvent {
+    pub entrant: Pubkey,
+    pub won: bool,
+    pub tokens: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct BuyCommittedEvent {
+    pub buyer: Pubkey,
+    pub escrow_usdc: u64,
+}
+
+#[event]
+pub struct CommitmentRefundedEvent {
+    pub buyer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OrderPlacedEvent {
+    pub owner: Pubkey,
+    pub batch_id: u64,
+    pub is_buy: bool,
+    pub amount_in: u64,
+    pub min_out: u64,
+}
+
+#[event]
+pub struct OrderCancelledEvent {
+    pub owner: Pubkey,
+    pub is_buy: bool,
+    pub amount_in: u64,
+}
+
+#[event]
+pub struct OrderRefundedEvent {
+    pub owner: Pubkey,
+    pub batch_id: u64,           // batch the order was placed in
+    pub is_buy: bool,
+    pub amount_in: u64,
+}
+
+#[event]
+pub struct BatchRefundClaimedEvent {
+    pub owner: Pubkey,
+    pub usdc: u64,
+    pub tokens: u64,
+}
+
+#[event]
+pub struct BatchSettledEvent {
+    pub launch: Pubkey,
+    pub batch_id: u64,
+    pub clearing_price: u64,     // USDC base units per whole token
+    pub buy_usdc: u64,
+    pub sell_tokens: u64,
+    pub filled_orders: u8,
+    pub carried_orders: u8,
+    pub refunded_orders: u8,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub launch: Pubkey,
+    pub reference_price: u128,
+    pub price: u128,
+    pub halted_until: i64,
+}
+
+#[event]
+pub struct CircuitBreakerCleared {
+    pub launch: Pubkey,
+    pub price: u128,
+}
+
+#[event]
+pub struct PauseUpdatedEvent {
+    pub launch: Option<Pubkey>,     // None => platform-wide flag
+    pub guardian: Pubkey,
+    pub previous_scopes: u8,
+    pub scopes: u8,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub previous: Pubkey,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct WoundDownEvent {
+    pub launch: Pubkey,
+    pub guardian: Pubkey,
+    pub forfeited_creator_usdc: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedeemEvent {
+    pub holder: Pubkey,
+    pub tokens_burned: u64,
+    pub usdc_out: u64,
+}
+
+#[event]
+pub struct BondPostedEvent {
+    pub launch: Pubkey,
+    pub bond_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BondSlashedEvent {
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub holders_usdc: u64,
+    pub creator_tokens_bought: u64,
+    pub creator_tokens_sold: u64,
+}
+
+#[event]
+pub struct BondReleasedEvent {
+    pub launch: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VestingCreatedEvent {
+    pub launch: Pubkey,
+    pub index: u8,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,
+}
+
+#[event]
+pub struct VestingClaimedEvent {
+    pub launch: Pubkey,
+    pub index: u8,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LockCreatedEvent {
+    pub lock: Pubkey,
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct LockDepositEvent {
+    pub lock: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LockExtendedEvent {
+    pub lock: Pubkey,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct LockReleasedEvent {
+    pub lock: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LockBeneficiaryChangedEvent {
+    pub lock: Pubkey,
+    pub previous: Pubkey,
+    pub beneficiary: Pubkey,
+}
+
+#[event]
+pub struct LpMigrationProposedEvent {
+    pub lp_lock: Pubkey,
+    pub target: Pubkey,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct LpMigrationApprovedEvent {
+    pub lp_lock: Pubkey,
+    pub target: Pubkey,
+    pub approver: Pubkey,
+}
+
+#[event]
+pub struct LpMigrationCancelledEvent {
+    pub lp_lock: Pubkey,
+    pub target: Pubkey,
+}
+
+#[event]
+pub struct LpMigratedEvent {
+    pub lp_lock: Pubkey,
+    pub from_amm: Pubkey,
+    pub to_amm: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,            // LP of the new pool now held by the lock
+}
+
+#[event]
+pub struct LpFeeConfigUpdatedEvent {
+    pub amm_adapter: Pubkey,
+    pub owner_bps: u16,
+    pub holders_bps: u16,
+}
+
+#[event]
+pub struct LpFeesHarvestedEvent {
+    pub lp_lock: Pubkey,
+    pub usdc_owner: u64,
+    pub usdc_holders: u64,
+    pub usdc_platform: u64,
+    pub tokens_owner: u64,
+    pub tokens_holders: u64,
+    pub tokens_platform: u64,
+}
+
+#[event]
+pub struct LpTokenFeesClaimedEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LiquidityBurned {
+    pub launch: Pubkey,
+    pub amm_id: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub token_amount: u64,
+    pub usdc_amount: u64,
+}
+
+#[event]
+pub struct LpLockExtendedEvent {
+    pub lp_lock: Pubkey,
+    pub additional_secs: i64,
+    pub final_unlock: i64,
+}
+
+#[event]
+pub struct SupplyFinalized {
+    pub launch: Pubkey,
+    pub mint: Pubkey,
+    pub supply: u64,
+    pub minted: u64,                // minted at finalization, never past total_supply
+    pub liquidity_minted: u64,      // part of `minted` left for the pool after owed claims
+    pub claims_reserved: u64,
+}
+
+#[event]
+pub struct MetadataUpdatedEvent {
+    pub launch: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct TickerClaimedEvent {
+    pub symbol: String,
+    pub launch: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct TickerReleasedEvent {
+    pub symbol: String,
+    pub launch: Pubkey,
+}
+
+#[event]
+pub struct TickerAssignedEvent {
+    pub symbol: String,
+    pub launch: Pubkey,
+    pub mint: Pubkey,
+    pub policy: TickerPolicy,
+}
+
+#[event]
+pub struct PhaseChanged {
+    pub launch: Pubkey,
+    pub from: LaunchPhase,
+    pub to: LaunchPhase,
+    pub timestamp: i64,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LaunchConfig {
     pub total_raised: u64,
-    pub closed: bool,
-    pub failed: bool,
+    pub phase: LaunchPhase,
     pub creator: Pubkey,
     pub platform_wallet: Pubkey,
     pub start_time: i64,
@@ -99,7 +596,6 @@ pub struct LaunchConfig {
     pub snipe_slippage: u16,  // unused currently
     pub auto_withdraw_threshold: u64,
     pub total_supply: u64,
-    pub migrated: bool,
     pub creator_paid_usdc: u64,
     pub platform_fees_collected: u64,
     pub platform_auto_transferred: u64,
@@ -109,805 +605,6402 @@ pub struct LaunchConfig {
     pub creator_reserve_usdc: u64,
     pub holders_reserve_usdc: u64,
     pub holders_index: u128, // cumulative USDC per token scaled
+    pub holders_reserve_tokens: u64,  // holders' share of harvested LP token fees, unclaimed
+    pub holders_token_index: u128,    // cumulative launch tokens per token held, scaled
+    // allowlist presale (merkle_root all-zero => no presale)
+    pub merkle_root: [u8; 32],
+    pub curve_start_time: i64,   // public curve opens here; equals start_time without presale/round
+    // fixed-price round (round_max_tokens == 0 => no round)
+    pub round_price: u64,        // USDC base units per whole token
+    pub round_max_tokens: u64,
+    pub round_wallet_cap: u64,   // tokens per wallet
+    pub round_soft_cap: u64,     // USDC
+    pub round_tokens_sold: u64,
+    pub round_raised: u64,
+    // batch auction (auction_tick_size == 0 => no auction); prices in USDC base units per whole token
+    pub auction_min_price: u64,
+    pub auction_tick_size: u64,
+    pub auction_clearing_price: u64,
+    pub auction_marginal_tick: u8,
+    pub auction_marginal_demand: u64, // total quote bid at the marginal tick
+    pub auction_marginal_fill: u64,   // portion of it that was filled
+    pub auction_quote_filled: u64,
+    pub auction_tokens_filled: u64,
+    // lottery (lottery_ticket_usdc == 0 => no lottery)
+    pub lottery_ticket_usdc: u64,
+    pub lottery_max_winners: u32,
+    pub lottery_entries: u32,
+    pub lottery_draw_slot: u64,     // committed future slot whose SlotHashes entry seeds the draw
+    pub lottery_seed: [u8; 32],
+    pub lottery_perm_a: u64,        // rank(i) = (a * i + b) mod entries, gcd(a, entries) == 1
+    pub lottery_perm_b: u64,
+    pub lottery_tokens_per_ticket: u64,
+    // liquidity bootstrapping: token weight in bps shifts linearly over the curve window (0 => constant product)
+    pub lbp_start_weight_bps: u16,
+    pub lbp_end_weight_bps: u16,
+    // commit-reveal opening (commit_window == 0 => disabled); windows start at curve_start_time
+    pub commit_window: i64,
+    pub reveal_window: i64,
+    // frequent batch trading (0 => direct buy/sell)
+    pub batch_interval_slots: u64,
+    pub sandwich_guard: bool,
+    // price-drop circuit breaker (breaker_drop_bps == 0 => disabled)
+    pub breaker_drop_bps: u16,
+    pub breaker_window_slots: u64,
+    pub breaker_cooldown: i64,
+    pub halted_until: i64,          // sells paused while now < halted_until
+    pub snapshot_cursor: u8,
+    pub price_snapshots: [PriceSnapshot; PRICE_SNAPSHOTS],
+    pub paused_scopes: u8,          // PAUSE_* bits set by the guardian for this launch
+    pub wound_down: bool,           // failed by the guardian; holders exit through redeem
+    // creator bond (bond_amount == 0 => none); held in the bond-vault PDA
+    pub bond_amount: u64,
+    pub bond_in_launch_token: bool, // false => USDC
+    pub bond_max_sell_bps: u16,     // of the creator's BuyerLedger tokens_bought
+    pub bond_holding_period: i64,   // after graduation
+    pub bond_posted: bool,
+    pub bond_settled: bool,         // released or slashed
+    pub graduated_at: i64,
+    // team / marketing allocations minted at creation into the vesting vault; schedules run from start_time
+    pub vesting_buckets: [VestingBucket; MAX_VESTING_BUCKETS],
+    pub vesting_total: u64,
+    pub vesting_claimed: u64,
+    // round / auction / lottery tokens delivered; the rest stays reserved in the curve inventory at graduation
+    pub presale_claimed: u64,
+    // mint authority revoked (at creation for fixed supply, else at graduation); from then on tokens
+    // are paid out of and returned to bonding_curve_ata instead of being minted and burned
+    pub supply_finalized: bool,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct BuyerLedger {
-    pub buyer: Pubkey,
-    pub last_claim: i64,
-    pub bump: u8,
-    pub paid_usdc: u64,
-    pub last_index_claimed: u128,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct VestingBucket {
+    pub beneficiary: Pubkey,        // default => unused slot
+    pub amount: u64,
+    pub claimed: u64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,         // linear release over [0, duration) after start; 0 => all at the cliff
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct LPLock {
-    pub amm_id: Pubkey,
-    pub lp_mint: Pubkey,
-    pub vault_ata: Pubkey,
-    pub unlock_timestamp: i64,
-    pub migration_allowed: bool,
-    pub migration_target: Pubkey,
-    pub authority: Pubkey,
-    pub bump: u8,
+impl VestingBucket {
+    pub fn vested(&self, start_time: i64, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(start_time);
+        if elapsed < self.cliff_secs {
+            return 0;
+        }
+        if elapsed >= self.duration_secs {
+            return self.amount;
+        }
+        ((self.amount as u128) * (elapsed as u128) / (self.duration_secs as u128)) as u64
+    }
 }
 
-const LOCK_DURATION: i64 = 5 * 365 * 24 * 60 * 60;
-const USDC_DEVNET: Pubkey = pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
-const PLATFORM_WALLET_PUBKEY: Pubkey = pubkey!("7XB2PEWYd5be12CpJ9e4ZTZTHCgNrcTbL7HigciPd1C6");
-const PLATFORM_FEE_USDC: u64 = 5_000_000;          // 5 USDC (6 decimals)
-const BURN_BUY_PCT: u8 = 1;                        // 1%
-const MIN_VIRTUAL_USDC: u64 = 10_000_000;          // 10 USDC
-const PLATFORM_AUTO_TRANSFER_THRESHOLD: u64 = 1_000_000_000; // 1000 USDC
-const ACC_SCALE: u128 = 1_000_000_000_000; // 1e12 scaling for holder index
-
-fn bonding_curve_buy(usdc_in: u64, virtual_usdc: u64, virtual_token: u64, _decimals: u8) -> u64 {
-    // simple constant-product k = virtual_usdc * virtual_token
-    let new_usdc = virtual_usdc.saturating_add(usdc_in);
-    let new_token = (virtual_token as u128)
-        .saturating_mul(virtual_usdc as u128)
-        .checked_div(new_usdc as u128)
-        .unwrap_or(0) as u64;
-    virtual_token.saturating_sub(new_token)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PriceSnapshot {
+    pub slot: u64,
+    pub price: u128,                // spot, USDC per token scaled by PRICE_SCALE
 }
 
-fn bonding_curve_sell(token_in: u64, virtual_usdc: u64, virtual_token: u64, _decimals: u8) -> u64 {
-    let new_token = virtual_token.saturating_add(token_in);
-    let new_usdc = (virtual_usdc as u128)
-        .saturating_mul(virtual_token as u128)
-        .checked_div(new_token as u128)
-        .unwrap_or(0) as u64;
-    virtual_usdc.saturating_sub(new_usdc)
-}
+impl LaunchConfig {
+    pub fn require_phase(&self, allowed: &[LaunchPhase]) -> Result<()> {
+        require!(allowed.contains(&self.phase), LaunchError::WrongPhase);
+        Ok(())
+    }
 
-#[derive(PartialEq)]
-pub enum CallerType {
-    Platform,
-    Creator,
-    Holder,
-}
+    // single entry point for lifecycle changes
+    pub fn transition(&mut self, launch: Pubkey, to: LaunchPhase) -> Result<()> {
+        let from = self.phase;
+        require!(from.can_transition_to(to), LaunchError::InvalidTransition);
+        self.phase = to;
+        emit!(PhaseChanged {
+            launch,
+            from,
+            to,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
 
-fn classify_caller(
-    caller: &Pubkey,
-    launch_config: &LaunchConfig,
-    _ledger: Option<&BuyerLedger>,
-) -> Result<CallerType> {
-    if *caller == PLATFORM_WALLET_PUBKEY {
-        return Ok(CallerType::Platform);
+    // time-driven transitions, applied lazily by the instructions that care
+    pub fn sync_phase(&mut self, launch: Pubkey, now: i64) -> Result<()> {
+        if self.phase == LaunchPhase::Scheduled && now >= self.start_time {
+            if self.has_presale() {
+                self.transition(launch, LaunchPhase::Presale)?;
+            } else if self.has_fixed_price_round() {
+                self.transition(launch, LaunchPhase::FixedPriceRound)?;
+            } else if self.has_batch_auction() {
+                self.transition(launch, LaunchPhase::BatchAuction)?;
+            } else if self.has_lottery() {
+                self.transition(launch, LaunchPhase::Lottery)?;
+            } else {
+                self.transition(launch, LaunchPhase::Curve)?;
+            }
+        }
+        if self.phase == LaunchPhase::Presale && now >= self.curve_start_time {
+            self.transition(launch, LaunchPhase::Curve)?;
+        }
+        if self.phase == LaunchPhase::FixedPriceRound
+            && (now >= self.curve_start_time || self.round_tokens_sold >= self.round_max_tokens)
+        {
+            self.close_fixed_price_round(launch, now)?;
+        }
+        Ok(())
     }
-    if *caller == launch_config.creator {
-        return Ok(CallerType::Creator);
+
+    pub fn has_presale(&self) -> bool {
+        self.merkle_root != [0u8; 32] && self.curve_start_time > self.start_time
     }
-    Ok(CallerType::Holder)
-}
 
-#[program]
-pub mod lumen_launch {
-    use super::*;
+    pub fn has_fixed_price_round(&self) -> bool {
+        self.round_max_tokens > 0
+    }
 
-    pub fn create_token(
-        ctx: Context<CreateToken>,
-        _usdc_mint: Pubkey,
-        virtual_usdc_amount: u64,
-    ) -> Result<()> {
-        require!(virtual_usdc_amount >= MIN_VIRTUAL_USDC, LaunchError::Min10USDC);
+    pub fn has_batch_auction(&self) -> bool {
+        self.auction_tick_size > 0
+    }
 
-        // 1) Collect fixed platform fee to platform USDC ATA
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.creator_usdc.to_account_info(),
-                    to: ctx.accounts.platform_usdc_ata.to_account_info(),
-                    authority: ctx.accounts.creator.to_account_info(),
-                },
-            ),
-            PLATFORM_FEE_USDC,
-        )?;
+    pub fn auction_tick_price(&self, tick: u8) -> u64 {
+        self.auction_min_price
+            .saturating_add(self.auction_tick_size.saturating_mul(tick as u64))
+    }
 
-        // 2) Seed USDC vault with virtual liquidity (kept in vault)
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.creator_usdc.to_account_info(),
-                    to: ctx.accounts.usdc_vault.to_account_info(),
-                    authority: ctx.accounts.creator.to_account_info(),
-                },
-            ),
-            virtual_usdc_amount,
-        )?;
+    // Walks ticks from the highest price down. Every bid at or above the clearing tick pays
+    // the average curve price P(Q) = (x + Q) / y for the aggregate quote Q; the first tick
+    // that cannot be fully absorbed is filled pro rata up to its price.
+    pub fn clear_auction(&self, demand: &[u64; AUCTION_TICKS]) -> AuctionClearing {
+        let x = self.virtual_usdc as u128;
+        let y = self.virtual_token as u128;
+        let unit = 10u128.pow(TOKEN_DECIMALS as u32);
+        let mut filled: u128 = 0;
+        let mut marginal_tick = 0u8;
+        let mut marginal_demand = 0u64;
+        let mut marginal_fill = 0u64;
+        for tick in (0..AUCTION_TICKS).rev() {
+            let d = demand[tick] as u128;
+            if d == 0 {
+                continue;
+            }
+            let price = self.auction_tick_price(tick as u8) as u128;
+            // quote at which the average curve price reaches this tick's price
+            let max_total = price
+                .saturating_mul(y)
+                .checked_div(unit)
+                .unwrap_or(0)
+                .saturating_sub(x);
+            let take = max_total.saturating_sub(filled).min(d);
+            marginal_tick = tick as u8;
+            marginal_demand = d as u64;
+            marginal_fill = take as u64;
+            filled = filled.saturating_add(take);
+            if take < d {
+                break;
+            }
+        }
 
-        let config = &mut ctx.accounts.launch_config;
-        let decimals = ctx.accounts.usdc_mint.decimals; // USDC decimals
-        let supply = 1_000_000_000u64
-            .checked_mul(10u64.pow(decimals as u32))
-            .ok_or(LaunchError::Overflow)?;
+        let tokens_filled = if filled > 0 {
+            bonding_curve_buy(filled as u64, self.virtual_usdc, self.virtual_token, self.usdc_decimals)
+        } else {
+            0
+        };
+        let clearing_price = if tokens_filled > 0 {
+            filled.saturating_mul(unit).checked_div(tokens_filled as u128).unwrap_or(0) as u64
+        } else {
+            0
+        };
+        AuctionClearing {
+            marginal_tick,
+            marginal_demand,
+            marginal_fill,
+            filled: filled as u64,
+            tokens_filled,
+            clearing_price,
+        }
+    }
 
-        config.total_supply = supply;
-        config.virtual_usdc = virtual_usdc_amount;
-        config.virtual_token = supply / 2;
-        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
-        config.start_time = Clock::get()?.unix_timestamp;
-        config.deadline = config.start_time + 72 * 60 * 60;
-        config.anti_snipe_blocks = 2;
-        config.snipe_max_pct = 10; // 0.1% of supply as default (in 1/10,000 => 10)
-        config.snipe_slippage = 50;
-        config.platform_wallet = PLATFORM_WALLET_PUBKEY;
-        config.creator = ctx.accounts.creator.key();
-        config.bump = ctx.bumps.launch_config;
-        config.usdc_decimals = decimals;
-        config.closed = false;
-        config.failed = false;
-        config.migrated = false;
-        config.creator_paid_usdc = virtual_usdc_amount;
-        config.platform_fees_collected = PLATFORM_FEE_USDC;
-        config.platform_auto_transferred = 0;
-        config.last_withdraw = 0;
-        config.in_trade = false;
-        config.auto_withdraw_threshold = PLATFORM_AUTO_TRANSFER_THRESHOLD;
-        config.creator_reserve_usdc = 0;
-        config.holders_reserve_usdc = 0;
-        config.holders_index = 0;
+    // quote filled for a bid of `quote_amount` at `tick`, given the settled marginal tick
+    pub fn auction_fill(&self, tick: u8, quote_amount: u64) -> u64 {
+        use core::cmp::Ordering::*;
+        match tick.cmp(&self.auction_marginal_tick) {
+            Greater => quote_amount,
+            Equal => (quote_amount as u128)
+                .saturating_mul(self.auction_marginal_fill as u128)
+                .checked_div(self.auction_marginal_demand as u128)
+                .unwrap_or(0) as u64,
+            Less => 0,
+        }
+    }
 
-        Ok(())
+    pub fn has_lottery(&self) -> bool {
+        self.lottery_ticket_usdc > 0
     }
 
-    pub fn buy(
-        ctx: Context<Buy>,
-        usdc_amount: u64,
-        min_tokens_out: u64,
-        deadline: i64,
-    ) -> Result<()> {
-        require!(usdc_amount > 0, LaunchError::ZeroAmount);
-        require!(Clock::get()?.unix_timestamp <= deadline, LaunchError::Deadline);
+    pub fn lottery_is_winner(&self, index: u32) -> bool {
+        if self.lottery_entries <= self.lottery_max_winners {
+            return true;
+        }
+        let rank = (self.lottery_perm_a as u128)
+            .saturating_mul(index as u128)
+            .saturating_add(self.lottery_perm_b as u128)
+            % self.lottery_entries as u128;
+        rank < self.lottery_max_winners as u128
+    }
 
-        let config = &mut ctx.accounts.launch_config;
-        require!(!config.closed, LaunchError::Ended);
-        require!(!config.in_trade, LaunchError::Reentrancy); // reentrancy guard
-        config.in_trade = true;
+    pub fn has_lbp(&self) -> bool {
+        self.lbp_start_weight_bps > 0
+    }
 
-        let decimals = config.usdc_decimals;
+    // token weight in bps at `now`, interpolated from curve_start_time to deadline
+    pub fn lbp_token_weight(&self, now: i64) -> u16 {
+        let span = self.deadline.saturating_sub(self.curve_start_time).max(1) as i128;
+        let elapsed = now.saturating_sub(self.curve_start_time).clamp(0, span as i64) as i128;
+        let start = self.lbp_start_weight_bps as i128;
+        let end = self.lbp_end_weight_bps as i128;
+        (start + (end - start) * elapsed / span) as u16
+    }
 
-        // calculate tokens out based on bonding curve
-        let tokens_out = bonding_curve_buy(usdc_amount, config.virtual_usdc, config.virtual_token, decimals);
-        require!(tokens_out >= min_tokens_out, LaunchError::Slippage);
+    pub fn curve_buy(&self, usdc_in: u64, now: i64) -> u64 {
+        if self.has_lbp() {
+            lbp_buy(usdc_in, self.virtual_usdc, self.virtual_token, self.lbp_token_weight(now))
+        } else {
+            bonding_curve_buy(usdc_in, self.virtual_usdc, self.virtual_token, self.usdc_decimals)
+        }
+    }
 
-        // anti-snipe: first 20s max 0.1% of supply
-        let time_passed = Clock::get()?.unix_timestamp - config.start_time;
-        if time_passed <= 20 {
-            // snipe_max_pct in 1/10,000 of supply; enforce tokens_out <= snipe_max_pct/10000 * total_supply
-            let max_tokens = (config.total_supply as u128)
-                .saturating_mul(config.snipe_max_pct as u128)
-                .checked_div(10_000)
-                .unwrap_or(0) as u64;
-            require!(tokens_out <= max_tokens, LaunchError::SnipeSize);
+    pub fn curve_sell(&self, token_in: u64, now: i64) -> u64 {
+        if self.has_lbp() {
+            lbp_sell(token_in, self.virtual_usdc, self.virtual_token, self.lbp_token_weight(now))
+        } else {
+            bonding_curve_sell(token_in, self.virtual_usdc, self.virtual_token, self.usdc_decimals)
         }
+    }
 
-        // burn on buy
-        let burn_amount = tokens_out.saturating_mul(BURN_BUY_PCT as u64) / 100;
-        let user_tokens = tokens_out.saturating_sub(burn_amount);
+    // update curve state after a buy
+    pub fn apply_buy(&mut self, usdc_in: u64, tokens_out: u64) {
+        self.virtual_usdc = self.virtual_usdc.saturating_add(usdc_in);
+        self.virtual_token = self.virtual_token.saturating_sub(tokens_out);
+        self.k = (self.virtual_usdc as u128) * (self.virtual_token as u128);
+        self.total_raised = self.total_raised.saturating_add(usdc_in);
+    }
 
-        // transfer USDC into the USDC vault
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.buyer_usdc.to_account_info(),
-                    to: ctx.accounts.usdc_vault.to_account_info(),
-                    authority: ctx.accounts.buyer.to_account_info(),
-                },
-            ),
-            usdc_amount,
-        )?;
+    pub fn has_commit_reveal(&self) -> bool {
+        self.commit_window > 0
+    }
 
-        // mint to buyer
-        token::mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.buyer_x_ata.to_account_info(),
-                    authority: ctx.accounts.mint_auth.to_account_info(),
-                },
-                &[&[b"mint-auth", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.mint_auth]]],
-            ),
-            user_tokens,
-        )?;
+    pub fn commit_end(&self) -> i64 {
+        self.curve_start_time.saturating_add(self.commit_window)
+    }
 
-        // mint and burn for fee
-        if burn_amount > 0 {
-            token::mint_to(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    MintTo {
-                        mint: ctx.accounts.mint.to_account_info(),
-                        to: ctx.accounts.burn_ata.to_account_info(),
-                        authority: ctx.accounts.mint_auth.to_account_info(),
-                    },
-                    &[&[b"mint-auth", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.mint_auth]]],
-                ),
-                burn_amount,
-            )?;
-            token::burn(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Burn {
-                        mint: ctx.accounts.mint.to_account_info(),
-                        from: ctx.accounts.burn_ata.to_account_info(),
-                        authority: ctx.accounts.burn_auth.to_account_info(),
-                    },
-                    &[&[b"mint-auth", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.mint_auth]]],
-                ),
-                burn_amount,
-            )?;
+    // public buy/sell opens here
+    pub fn reveal_end(&self) -> i64 {
+        self.commit_end().saturating_add(self.reveal_window)
+    }
+
+    // split a sell tax into the reserves; returns the holders share
+    pub fn accrue_sell_tax(&mut self, tax: u64) -> u64 {
+        let platform_share = (tax as u128).saturating_mul(20).checked_div(100).unwrap_or(0) as u64;
+        let creator_share = (tax as u128).saturating_mul(30).checked_div(100).unwrap_or(0) as u64;
+        let holders_share = tax.saturating_sub(platform_share).saturating_sub(creator_share);
+
+        // Update reserves and platform accounting
+        self.creator_reserve_usdc = self.creator_reserve_usdc.saturating_add(creator_share);
+        self.holders_reserve_usdc = self.holders_reserve_usdc.saturating_add(holders_share);
+        self.platform_fees_collected = self.platform_fees_collected.saturating_add(platform_share);
+        holders_share
+    }
+
+    // update holders accumulator (distribute holders_share to current holders)
+    pub fn accrue_holders_index(&mut self, holders_share: u64, supply: u64) {
+        self.holders_index = self.holders_index.saturating_add(index_increment(holders_share, supply));
+    }
+
+    // same for the token side of harvested LP fees, held in the holders token vault
+    pub fn accrue_holders_token_index(&mut self, holders_share: u64, supply: u64) {
+        self.holders_reserve_tokens = self.holders_reserve_tokens.saturating_add(holders_share);
+        self.holders_token_index = self.holders_token_index.saturating_add(index_increment(holders_share, supply));
+    }
+
+    pub fn spot_price(&self, now: i64) -> u128 {
+        self.price_at(self.virtual_usdc, self.virtual_token, now)
+    }
+
+    // spot price for the given virtual reserves, e.g. after a trade that has not run yet
+    pub fn price_at(&self, virtual_usdc: u64, virtual_token: u64, now: i64) -> u128 {
+        let (mut num, mut den) = (virtual_usdc as u128, virtual_token as u128);
+        if self.has_lbp() {
+            let wt = self.lbp_token_weight(now) as u128;
+            num = num.saturating_mul(wt);
+            den = den.saturating_mul(10_000u128.saturating_sub(wt));
         }
+        num.saturating_mul(PRICE_SCALE).checked_div(den).unwrap_or(0)
+    }
 
-        // update curve state
-        config.virtual_usdc = config.virtual_usdc.saturating_add(usdc_amount);
-        config.virtual_token = config.virtual_token.saturating_sub(tokens_out);
-        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
-        config.total_raised = config.total_raised.saturating_add(usdc_amount);
+    pub fn has_circuit_breaker(&self) -> bool {
+        self.breaker_drop_bps > 0
+    }
 
-        // ensure buyer ledger exists and update paid_usdc
-        let ledger = &mut ctx.accounts.buyer_ledger;
-        ledger.paid_usdc = ledger.paid_usdc.saturating_add(usdc_amount);
+    pub fn breaker_halted(&self, now: i64) -> bool {
+        now < self.halted_until
+    }
 
-        emit!(BuyEvent {
-            buyer: ctx.accounts.buyer.key(),
-            usdc_in: usdc_amount,
-            tokens_out: user_tokens,
-            burned: burn_amount,
-        });
+    // Called after every curve trade: clears an expired halt, trips when the spot price
+    // is more than breaker_drop_bps below the highest snapshot inside the slot window,
+    // and records a new snapshot every window / PRICE_SNAPSHOTS slots.
+    pub fn update_breaker(&mut self, launch: Pubkey, now: i64, slot: u64) -> Result<()> {
+        if !self.has_circuit_breaker() {
+            return Ok(());
+        }
+        let price = self.spot_price(now);
+        if self.halted_until != 0 && now >= self.halted_until {
+            self.halted_until = 0;
+            self.price_snapshots = [PriceSnapshot::default(); PRICE_SNAPSHOTS];
+            emit!(CircuitBreakerCleared { launch, price });
+        }
 
-        // finalize if after deadline
-        let now = Clock::get()?.unix_timestamp;
-        if now > config.deadline && !config.closed {
-            let total_raised = config.total_raised;
-            if total_raised < 5_000_000_000 { // < 5,000 USDC
-                config.failed = true;
-                config.closed = true;
-                emit!(LaunchFailedEvent {
-                    launch: config.key(),
-                    total_raised,
-                });
-                // refund creator virtual funds from vault if available
-                let creator_refund = config.creator_paid_usdc;
-                let available = ctx.accounts.usdc_vault.amount;
-                let refund = core::cmp::min(creator_refund, available);
-                if refund > 0 {
-                    token::transfer(
-                        CpiContext::new_with_signer(
-                            ctx.accounts.token_program.to_account_info(),
-                            Transfer {
-                                from: ctx.accounts.usdc_vault.to_account_info(),
-                                to: ctx.accounts.creator_usdc_ata.to_account_info(),
-                                authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                            },
-                            &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-                        ),
-                        refund,
-                    )?;
-                    config.creator_paid_usdc = config.creator_paid_usdc.saturating_sub(refund);
-                }
-            } else {
-                config.failed = false;
-                config.closed = true;
-                emit!(LaunchSucceededEvent {
-                    launch: config.key(),
-                    total_raised,
-                });
-            }
+        let reference = self.breaker_reference(slot);
+        if self.halted_until == 0 && price < self.breaker_trip_price(reference) {
+            self.halted_until = now.saturating_add(self.breaker_cooldown);
+            emit!(CircuitBreakerTripped {
+                launch,
+                reference_price: reference,
+                price,
+                halted_until: self.halted_until,
+            });
         }
 
-        config.in_trade = false;
+        let latest = self.price_snapshots[self.snapshot_cursor as usize];
+        let interval = (self.breaker_window_slots / PRICE_SNAPSHOTS as u64).max(1);
+        if latest.price == 0 || slot >= latest.slot.saturating_add(interval) {
+            self.snapshot_cursor = ((self.snapshot_cursor as usize + 1) % PRICE_SNAPSHOTS) as u8;
+            self.price_snapshots[self.snapshot_cursor as usize] = PriceSnapshot { slot, price };
+        }
         Ok(())
     }
 
-    pub fn sell(
-        ctx: Context<Sell>,
-        token_amount: u64,
-        min_usdc_out: u64,
-        deadline: i64,
-    ) -> Result<()> {
-        require!(token_amount > 0, LaunchError::ZeroAmount);
-        require!(Clock::get()?.unix_timestamp <= deadline, LaunchError::Deadline);
-
-        let config = &mut ctx.accounts.launch_config;
-        require!(!config.closed, LaunchError::Ended);
-        require!(!config.in_trade, LaunchError::Reentrancy);
-        config.in_trade = true;
+    // highest snapshot inside the slot window
+    fn breaker_reference(&self, slot: u64) -> u128 {
+        let floor_slot = slot.saturating_sub(self.breaker_window_slots);
+        self.price_snapshots
+            .iter()
+            .filter(|s| s.price > 0 && s.slot >= floor_slot)
+            .map(|s| s.price)
+            .max()
+            .unwrap_or(0)
+    }
 
-        let decimals = config.usdc_decimals;
-        let usdc_out = bonding_curve_sell(token_amount, config.virtual_usdc, config.virtual_token, decimals);
-        require!(usdc_out >= min_usdc_out, LaunchError::Slippage);
+    fn breaker_trip_price(&self, reference: u128) -> u128 {
+        reference.saturating_mul(10_000 - self.breaker_drop_bps as u128) / 10_000
+    }
 
-        // burn seller tokens
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    from: ctx.accounts.seller_x_ata.to_account_info(),
-                    authority: ctx.accounts.seller.to_account_info(),
-                },
-            ),
-            token_amount,
-        )?;
+    // Checked before a sell executes: a trade that would by itself take the price below the
+    // trip level is rejected, so one large sell cannot crash the price ahead of the breaker.
+    pub fn check_breaker(&self, price_after: u128, now: i64, slot: u64) -> Result<()> {
+        if !self.has_circuit_breaker() {
+            return Ok(());
+        }
+        // snapshots are dropped when an expired halt clears; the current price always counts
+        let expired_halt = self.halted_until != 0 && now >= self.halted_until;
+        let snapshots = if expired_halt { 0 } else { self.breaker_reference(slot) };
+        let reference = snapshots.max(self.spot_price(now));
+        require!(price_after >= self.breaker_trip_price(reference), LaunchError::BreakerLimit);
+        Ok(())
+    }
 
-        // dynamic tax tiers by trade size vs total supply
-        let total_supply = config.total_supply;
-        let sell_percentage = (token_amount as u128)
-            .saturating_mul(100)
-            .checked_div(total_supply as u128)
-            .unwrap_or(0) as u64;
-        let tax_rate = match sell_percentage {
-            0..=30 => 5,      // 0.5 %
-            31..=70 => 10,    // 1 %
-            _ => 30,          // 3 %
-        };
-        let tax = (usdc_out as u128)
-            .saturating_mul(tax_rate as u128)
-            .checked_div(1000)
-            .unwrap_or(0) as u64;
+    pub fn has_bond(&self) -> bool {
+        self.bond_amount > 0
+    }
 
-        let platform_share = (tax as u128).saturating_mul(20).checked_div(100).unwrap_or(0) as u64;
-        let creator_share = (tax as u128).saturating_mul(30).checked_div(100).unwrap_or(0) as u64;
-        let holders_share = tax.saturating_sub(platform_share).saturating_sub(creator_share);
+    pub fn bond_mint(&self, mint: Pubkey) -> Pubkey {
+        if self.bond_in_launch_token {
+            mint
+        } else {
+            USDC_DEVNET
+        }
+    }
 
-        // Update reserves and platform accounting
-        config.creator_reserve_usdc = config.creator_reserve_usdc.saturating_add(creator_share);
-        config.holders_reserve_usdc = config.holders_reserve_usdc.saturating_add(holders_share);
-        config.platform_fees_collected = config.platform_fees_collected.saturating_add(platform_share);
+    // Creator dumped more than the allowed share of what their ledger shows they bought.
+    pub fn bond_violated(&self, creator_ledger: &BuyerLedger) -> bool {
+        self.wound_down
+            || (creator_ledger.tokens_sold as u128).saturating_mul(10_000)
+                > (creator_ledger.tokens_bought as u128).saturating_mul(self.bond_max_sell_bps as u128)
+    }
 
-        // Auto transfer platform share if threshold reached and liquidity allows
-        let available_for_platform = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
-        if available_for_platform >= config.auto_withdraw_threshold {
-            let reserved = config.holders_reserve_usdc.saturating_add(config.creator_reserve_usdc);
-            let vault_bal = ctx.accounts.usdc_vault.amount;
-            let free_liquidity = vault_bal.saturating_sub(reserved);
-            let amount = core::cmp::min(available_for_platform, free_liquidity);
-            if amount > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.usdc_vault.to_account_info(),
-                            to: ctx.accounts.platform_profit_ata.to_account_info(),
-                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                        },
-                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-                    ),
-                    amount,
-                )?;
-                config.platform_auto_transferred = config.platform_auto_transferred.saturating_add(amount);
-                config.virtual_usdc = config.virtual_usdc.saturating_sub(amount);
-                config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+    // Picks the orders a batch fills among those marked `included` and returns the uniform
+    // price P = num / den (USDC per token, base units). Orders whose min_out fails at P drop out;
+    // if the result would take the price through the circuit breaker, the latest sells drop out
+    // until it passes.
+    pub fn match_batch(&self, orders: &[BatchOrder], included: &mut [bool], now: i64, slot: u64) -> (u128, u128) {
+        let x = self.virtual_usdc as u128;
+        let y = self.virtual_token as u128;
+        loop {
+            let (num, den) = loop {
+                let (mut b, mut s) = (0u128, 0u128);
+                for (o, _) in orders.iter().zip(included.iter()).filter(|(_, inc)| **inc) {
+                    if o.is_buy { b += o.amount_in as u128 } else { s += o.amount_in as u128 }
+                }
+                let (num, den) = (x + b, y + s);
+                let mut changed = false;
+                for (o, inc) in orders.iter().zip(included.iter_mut()).filter(|(_, inc)| **inc) {
+                    let out = if o.is_buy {
+                        (o.amount_in as u128) * den / num
+                    } else {
+                        (o.amount_in as u128) * num / den
+                    };
+                    if out < o.min_out as u128 {
+                        *inc = false;
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break (num, den);
+                }
+            };
+            let price_after = self.price_at((num * y / den) as u64, (x * den / num) as u64, now);
+            if self.check_breaker(price_after, now, slot).is_ok() {
+                return (num, den);
+            }
+            match orders.iter().zip(included.iter_mut()).rev().find(|(o, inc)| **inc && !o.is_buy) {
+                Some((_, inc)) => *inc = false,
+                None => return (num, den),
             }
         }
+    }
 
-        // compute user payout after tax
-        let user_usdc = usdc_out.saturating_sub(tax);
+    pub fn has_batch_trading(&self) -> bool {
+        self.batch_interval_slots > 0
+    }
 
-        // pay user from vault
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.usdc_vault.to_account_info(),
-                    to: ctx.accounts.seller_usdc_ata.to_account_info(),
-                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                },
-                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-            ),
-            user_usdc,
-        )?;
+    pub fn round_succeeded(&self) -> bool {
+        self.round_raised >= self.round_soft_cap
+    }
 
-        // update curve state
-        config.virtual_usdc = config.virtual_usdc.saturating_sub(usdc_out);
-        config.virtual_token = config.virtual_token.saturating_add(token_amount);
-        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+    // presale allocations not yet claimed; auction claims round down so this may leave dust
+    pub fn presale_owed(&self) -> u64 {
+        let round = if self.round_succeeded() { self.round_tokens_sold } else { 0 };
+        let lottery = if self.has_lottery() {
+            (self.lottery_entries.min(self.lottery_max_winners) as u64).saturating_mul(self.lottery_tokens_per_ticket)
+        } else {
+            0
+        };
+        round
+            .saturating_add(self.auction_tokens_filled)
+            .saturating_add(lottery)
+            .saturating_sub(self.presale_claimed)
+    }
 
-        // update holders accumulator (distribute holders_share to current holders)
-        let new_supply = ctx.accounts.mint.supply; // post-burn supply
-        if new_supply > 0 && holders_share > 0 {
-            let inc = (holders_share as u128)
-                .saturating_mul(ACC_SCALE)
-                .checked_div(new_supply as u128)
-                .unwrap_or(0);
-            config.holders_index = config.holders_index.saturating_add(inc);
+    // An auction that never settled or a lottery that was never drawn: every bid and ticket
+    // becomes a full refund (no tick reaches the marginal tick, no ticket wins).
+    pub fn void_opening_sale(&mut self) {
+        match self.phase {
+            LaunchPhase::BatchAuction => self.auction_marginal_tick = u8::MAX,
+            LaunchPhase::Lottery => self.lottery_max_winners = 0,
+            _ => {}
         }
+    }
 
-        emit!(SellEvent {
-            seller: ctx.accounts.seller.key(),
-            tokens_in: token_amount,
-            usdc_out: user_usdc,
-            tax: tax,
-        });
-
-        config.in_trade = false;
-        Ok(())
+    // seed the curve so its spot price continues from the round price, or fail the launch
+    fn close_fixed_price_round(&mut self, launch: Pubkey, now: i64) -> Result<()> {
+        if !self.round_succeeded() {
+            self.transition(launch, LaunchPhase::Failed)?;
+            emit!(LaunchFailedEvent { launch, total_raised: self.round_raised });
+            return Ok(());
+        }
+        // sold out early: the curve opens now
+        self.curve_start_time = self.curve_start_time.min(now);
+        self.virtual_token = round_seed_tokens(self.virtual_usdc, self.round_price, self.round_tokens_sold) as u64;
+        self.virtual_usdc = self.virtual_usdc.saturating_add(self.round_raised);
+        self.k = (self.virtual_usdc as u128) * (self.virtual_token as u128);
+        self.total_raised = self.total_raised.saturating_add(self.round_raised);
+        self.transition(launch, LaunchPhase::Curve)
     }
+}
 
-    pub fn claim_profits(ctx: Context<ClaimProfits>) -> Result<()> {
-        let user_balance = ctx.accounts.user_x_ata.amount; // current token holdings
-        require!(user_balance > 0, LaunchError::ZeroHolding);
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerLedger {
+    pub buyer: Pubkey,
+    pub last_claim: i64,
+    pub bump: u8,
+    pub paid_usdc: u64,
+    pub last_index_claimed: u128,
+    pub presale_paid_usdc: u64,
+    pub round_tokens: u64,       // bought in fixed-price round, not yet claimed
+    pub round_paid_usdc: u64,    // refundable if the round misses its soft cap
+    pub tokens_bought: u64,      // curve buys, reveals, round claims and filled batch buys
+    pub tokens_sold: u64,        // curve sells and filled batch sells
+    pub batch_refund_usdc: u64,  // expired or undeliverable batch orders, still in the batch escrow
+    pub batch_refund_tokens: u64,
+    pub last_token_index_claimed: u128,
+}
 
-        let config = &mut ctx.accounts.launch_config;
-        let ledger = &mut ctx.accounts.buyer_ledger;
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct LaunchParams {
+    pub start_time: Option<i64>, // None => trading opens immediately
+    pub presale: Option<PresaleParams>,
+    pub fixed_price_round: Option<FixedPriceRoundParams>,
+    pub batch_auction: Option<BatchAuctionParams>,
+    pub lottery: Option<LotteryParams>,
+    pub lbp: Option<LbpParams>,
+    pub commit_reveal: Option<CommitRevealParams>,
+    pub batch_trading: Option<BatchTradingParams>,
+    pub sandwich_guard: bool,
+    pub circuit_breaker: Option<CircuitBreakerParams>,
+    pub creator_bond: Option<CreatorBondParams>,
+    pub vesting: Vec<VestingBucketParams>,
+    pub fixed_supply: bool,          // mint everything into bonding_curve_ata up front and revoke the mint
+    pub metadata: TokenMetadataParams,
+}
 
-        // compute claimable using accumulator (bounded by reserves and free liquidity)
-        let delta_index = config.holders_index.saturating_sub(ledger.last_index_claimed);
-        let theoretical = (user_balance as u128)
-            .saturating_mul(delta_index)
-            .checked_div(ACC_SCALE)
-            .unwrap_or(0) as u64;
-        let reserved_holders = config.holders_reserve_usdc;
-        let reserved_platform = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
-        let reserved_creator = config.creator_reserve_usdc;
-        let vault_bal = ctx.accounts.usdc_vault.amount;
-        let free_liquidity = vault_bal
-            .saturating_sub(reserved_platform)
-            .saturating_sub(reserved_creator);
-        let claim_amount = core::cmp::min(theoretical, core::cmp::min(reserved_holders, free_liquidity));
-        require!(claim_amount > 0, LaunchError::ZeroEntitled);
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct TokenMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
 
-        // update ledger and reserves
-        ledger.last_claim = Clock::get()?.unix_timestamp;
-        ledger.last_index_claimed = config.holders_index;
-        config.holders_reserve_usdc = config.holders_reserve_usdc.saturating_sub(claim_amount);
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PresaleParams {
+    pub merkle_root: [u8; 32],
+    pub duration: i64,
+}
 
-        // pay user
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.usdc_vault.to_account_info(),
-                    to: ctx.accounts.user_usdc_ata.to_account_info(),
-                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                },
-                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-            ),
-            claim_amount,
-        )?;
-        config.virtual_usdc = config.virtual_usdc.saturating_sub(claim_amount);
-        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FixedPriceRoundParams {
+    pub price: u64,              // USDC base units per whole token
+    pub max_tokens: u64,
+    pub wallet_cap_tokens: u64,
+    pub soft_cap_usdc: u64,
+    pub duration: i64,
+}
 
-        emit!(ClaimEvent { user: ctx.accounts.user.key(), amount: claim_amount });
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchAuctionParams {
+    pub min_price: u64,          // price of tick 0, USDC base units per whole token
+    pub tick_size: u64,
+    pub duration: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LotteryParams {
+    pub ticket_usdc: u64,
+    pub max_winners: u32,
+    pub duration: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LbpParams {
+    pub start_weight_bps: u16,   // token weight at curve open, e.g. 9_600
+    pub end_weight_bps: u16,     // token weight at deadline, e.g. 5_000
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CommitRevealParams {
+    pub commit_window: i64,
+    pub reveal_window: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchTradingParams {
+    pub interval_slots: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CircuitBreakerParams {
+    pub drop_bps: u16,
+    pub window_slots: u64,
+    pub cooldown_secs: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatorBondParams {
+    pub in_launch_token: bool,
+    pub amount: u64,
+    pub max_sell_bps: u16,
+    pub holding_period: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VestingBucketParams {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub cliff_secs: i64,
+    pub duration_secs: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AllowlistProof {
+    pub allocation_usdc: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+// aggregate demand per price tick, so settlement is O(ticks) regardless of bid count
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionBook {
+    pub launch: Pubkey,
+    pub bump: u8,
+    pub demand: [u64; AUCTION_TICKS],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionBid {
+    pub bidder: Pubkey,
+    pub tick: u8,
+    pub quote_amount: u64,
+    pub bump: u8,
+    pub refunded: bool,         // unfilled quote already returned while claims were paused
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LotteryTicket {
+    pub entrant: Pubkey,
+    pub index: u32,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BuyCommitment {
+    pub buyer: Pubkey,
+    pub commitment: [u8; 32],   // keccak(buyer || usdc_amount || min_tokens_out || salt)
+    pub escrow_usdc: u64,       // may exceed the revealed amount to hide it
+    pub bump: u8,
+}
+
+// outcome of LaunchConfig::clear_auction
+pub struct AuctionClearing {
+    pub marginal_tick: u8,
+    pub marginal_demand: u64,
+    pub marginal_fill: u64,
+    pub filled: u64,            // aggregate quote filled
+    pub tokens_filled: u64,
+    pub clearing_price: u64,    // USDC base units per whole token
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BatchOrder {
+    pub owner: Pubkey,
+    pub destination: Pubkey,    // token account receiving the fill
+    pub is_buy: bool,
+    pub amount_in: u64,         // USDC for buys, tokens for sells (escrowed)
+    pub min_out: u64,
+    pub batch_id: u64,          // placed in this batch; refunded after MAX_ORDER_BATCHES
+}
+
+impl BatchOrder {
+    // unfilled at the settlement of `batch_id`, which is its last one
+    pub fn expired(&self, batch_id: u64) -> bool {
+        batch_id.saturating_sub(self.batch_id) + 1 >= MAX_ORDER_BATCHES
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub launch: Pubkey,
+    pub bump: u8,
+    pub batch_id: u64,
+    pub batch_start_slot: u64,
+    #[max_len(MAX_BATCH_ORDERS)]
+    pub orders: Vec<BatchOrder>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct SlotTrade {
+    pub trader: Pubkey,
+    pub slot: u64,
+    pub is_buy: bool,
+}
+
+// ring of the most recent curve trades, used to spot same-slot round trips
+#[account]
+#[derive(InitSpace)]
+pub struct SlotActivity {
+    pub launch: Pubkey,
+    pub bump: u8,
+    pub cursor: u8,
+    pub trades: [SlotTrade; SLOT_ACTIVITY_LEN],
+}
+
+impl SlotActivity {
+    pub fn record(&mut self, trader: Pubkey, slot: u64, is_buy: bool) -> Result<()> {
+        let round_trip = self
+            .trades
+            .iter()
+            .any(|t| t.trader == trader && t.slot == slot && t.is_buy != is_buy);
+        require!(!round_trip, LaunchError::RoundTrip);
+        self.trades[self.cursor as usize] = SlotTrade { trader, slot, is_buy };
+        self.cursor = ((self.cursor as usize + 1) % SLOT_ACTIVITY_LEN) as u8;
         Ok(())
     }
+}
 
-    pub fn reclaim_virtual_funds(ctx: Context<ReclaimVirtualFunds>) -> Result<()> {
-        let caller = ctx.accounts.creator.key();
-        let config = &mut ctx.accounts.launch_config;
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub authority: Pubkey,
+    pub guardian: Pubkey,
+    pub paused_scopes: u8,          // PAUSE_* bits applied to every launch
+    pub bump: u8,
+    // LP fee harvesting: only this program may be invoked with the LP lock as signer
+    pub amm_adapter: Pubkey,
+    pub lp_fee_owner_bps: u16,
+    pub lp_fee_holders_bps: u16,    // platform gets the remainder
+}
 
-        let caller_type = classify_caller(&caller, config, None)?;
-        require!(caller_type == CallerType::Creator, LaunchError::NotYourLedger);
-        require!(config.closed || config.migrated, LaunchError::NotEnded);
+impl PlatformConfig {
+    // (position owner, holders, platform)
+    pub fn split_lp_fees(&self, amount: u64) -> (u64, u64, u64) {
+        let owner = ((amount as u128) * (self.lp_fee_owner_bps as u128) / 10_000) as u64;
+        let holders = ((amount as u128) * (self.lp_fee_holders_bps as u128) / 10_000) as u64;
+        (owner, holders, amount.saturating_sub(owner).saturating_sub(holders))
+    }
 
-        let creator_refund = config.creator_paid_usdc;
-        if creator_refund > 0 {
-            let available = ctx.accounts.usdc_vault.amount;
-            let refund = core::cmp::min(creator_refund, available);
-            if refund > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.usdc_vault.to_account_info(),
-                            to: ctx.accounts.creator_usdc_ata.to_account_info(),
-                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                        },
-                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-                    ),
-                    refund,
-                )?;
-                config.creator_paid_usdc = config.creator_paid_usdc.saturating_sub(refund);
-            }
-        }
+    // Refund paths never call this, so escrowed funds can always be pulled back out:
+    // refund_fixed_price, reclaim_commitment, cancel_order, claim_batch_refund,
+    // reclaim_virtual_funds and the USDC refund side of claim_auction and claim_lottery.
+    pub fn require_unpaused(&self, launch: &LaunchConfig, scope: u8) -> Result<()> {
+        require!(
+            (self.paused_scopes | launch.paused_scopes) & scope == 0,
+            LaunchError::Paused
+        );
         Ok(())
     }
+}
 
-    pub fn close_and_migrate_to_raydium(
-        ctx: Context<CloseAndMigrateToRaydium>,
-    ) -> Result<()> {
-        let creator_refund = ctx.accounts.launch_config.creator_paid_usdc;
+// General-purpose locker: tokens sit in the lock's own ATA and unlock linearly
+// between start_time and end_time, with nothing released before cliff_time.
+#[account]
+#[derive(InitSpace)]
+pub struct TokenLock {
+    pub owner: Pubkey,              // may top up and extend
+    pub beneficiary: Pubkey,        // receives releases
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub id: u64,
+    pub deposited: u64,
+    pub released: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
 
-        // Read immutable state and prepare seeds before taking a mutable borrow
-        require!(ctx.accounts.launch_config.closed, LaunchError::NotEnded);
-        require!(!ctx.accounts.launch_config.migrated, LaunchError::MigrationNotAllowed);
+impl TokenLock {
+    pub fn unlocked(&self, now: i64) -> u64 {
+        if now < self.cliff_time {
+            return 0;
+        }
+        if now >= self.end_time {
+            return self.deposited;
+        }
+        let elapsed = (now - self.start_time) as u128;
+        let span = (self.end_time - self.start_time) as u128;
+        ((self.deposited as u128) * elapsed / span) as u64
+    }
 
-        // split balances (50%)
-        let token_half = ctx.accounts.bonding_curve_ata.amount / 2;
-        let usdc_half = ctx.accounts.usdc_vault.amount / 2;
+    pub fn releasable(&self, now: i64) -> u64 {
+        self.unlocked(now).saturating_sub(self.released)
+    }
 
-        // Seeds and authority for launch_config PDA
-        let lc_bump = ctx.accounts.launch_config.bump;
-        let mint_key = ctx.accounts.mint.key();
-        let lc_ai = ctx.accounts.launch_config.to_account_info();
-        let lc_bump_arr = [lc_bump];
-        let lc_seeds: [&[u8]; 3] = [b"launch", mint_key.as_ref(), &lc_bump_arr];
+    // Re-expresses deposited/released in units of a new vault balance (e.g. after an LP
+    // migration turned `from` old LP into `to` new LP), keeping the released share.
+    pub fn rescale(&mut self, from: u64, to: u64) {
+        let scale = |v: u64| ((v as u128) * (to as u128) / (from as u128).max(1)) as u64;
+        self.deposited = scale(self.deposited);
+        self.released = scale(self.released);
+    }
+}
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.bonding_curve_ata.to_account_info(),
-                    to: ctx.accounts.coin_vault.to_account_info(),
-                    authority: lc_ai,
-                },
-                &[&lc_seeds],
-            ),
-            token_half,
-        )?;
+// Custody check after handing the lock's signer to an outside program: the vault must still be
+// the lock's own, with nobody able to move or close it later.
+fn require_lock_custody(vault: &TokenAccount, token_lock: Pubkey) -> Result<()> {
+    require!(
+        vault.owner == token_lock && vault.delegate.is_none() && vault.close_authority.is_none(),
+        LaunchError::PrincipalMoved
+    );
+    Ok(())
+}
 
-        // move USDC from vault
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.usdc_vault.to_account_info(),
-                    to: ctx.accounts.pc_vault.to_account_info(),
-                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                },
-                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-            ),
-            usdc_half,
-        )?;
+// Graduated LP position. Custody and schedule live in the TokenLock owned by this account;
+// control belongs to whoever holds the 1-of-1 position token.
+#[account]
+#[derive(InitSpace)]
+pub struct LPLock {
+    pub amm_id: Pubkey,             // pool at graduation; seeds this account
+    pub launch: Pubkey,
+    pub current_amm: Pubkey,
+    pub token_lock: Pubkey,
+    pub position_mint: Pubkey,
+    pub tranches: [LpTranche; MAX_LP_TRANCHES], // cumulative release steps, ascending unlock_time
+    pub tranche_count: u8,
+    pub migration_allowed: bool,    // set by platform approval of the pending proposal
+    pub migration_target: Pubkey,
+    pub migration_proposed_at: i64, // 0 => no pending proposal
+    pub burned: bool,               // LP burned at graduation; nothing left to migrate
+    pub bump: u8,
+}
 
-        // refund creator virtual funds from vault if available
-        if creator_refund > 0 {
-            let available = ctx.accounts.usdc_vault.amount;
-            let refund = core::cmp::min(creator_refund, available);
-            if refund > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.usdc_vault.to_account_info(),
-                            to: ctx.accounts.creator_usdc_ata.to_account_info(),
-                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                        },
-                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-                    ),
-                    refund,
-                )?;
-                let cfg = &mut ctx.accounts.launch_config;
-                cfg.creator_paid_usdc = cfg.creator_paid_usdc.saturating_sub(refund);
-            }
-        }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct LpTranche {
+    pub unlock_time: i64,
+    pub bps: u16,                   // share of deposited LP released at unlock_time
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LpTrancheParams {
+    pub delay_secs: i64,            // after graduation
+    pub bps: u16,
+}
+
+impl LPLock {
+    pub fn is_position_holder(&self, position_ata: &TokenAccount, holder: Pubkey) -> bool {
+        position_ata.mint == self.position_mint && position_ata.owner == holder && position_ata.amount == 1
+    }
+
+    pub fn unlocked(&self, deposited: u64, now: i64) -> u64 {
+        let bps: u64 = self.tranches[..self.tranche_count as usize]
+            .iter()
+            .filter(|t| now >= t.unlock_time)
+            .map(|t| t.bps as u64)
+            .sum();
+        ((deposited as u128) * (bps as u128) / 10_000) as u64
+    }
+
+    pub fn final_unlock(&self) -> i64 {
+        self.tranches[..self.tranche_count as usize].last().map_or(0, |t| t.unlock_time)
+    }
+}
+
+// Empty schedule => everything unlocks after LOCK_DURATION. A custom schedule may not release
+// anything during the first MIN_LP_TRANCHE_DELAY.
+fn lp_unlock_tranches(now: i64, schedule: &[LpTrancheParams]) -> Result<([LpTranche; MAX_LP_TRANCHES], u8)> {
+    let mut tranches = [LpTranche::default(); MAX_LP_TRANCHES];
+    if schedule.is_empty() {
+        tranches[0] = LpTranche { unlock_time: now + LOCK_DURATION, bps: 10_000 };
+        return Ok((tranches, 1));
+    }
+    require!(schedule.len() <= MAX_LP_TRANCHES, LaunchError::InvalidUnlockSchedule);
+    let mut total_bps = 0u32;
+    let mut last_delay = MIN_LP_TRANCHE_DELAY - 1;
+    for (i, t) in schedule.iter().enumerate() {
+        require!(
+            t.bps > 0 && t.delay_secs > last_delay && t.delay_secs <= MAX_LOCK_DURATION,
+            LaunchError::InvalidUnlockSchedule
+        );
+        last_delay = t.delay_secs;
+        total_bps += t.bps as u32;
+        tranches[i] = LpTranche { unlock_time: now + t.delay_secs, bps: t.bps };
+    }
+    require!(total_bps == 10_000, LaunchError::InvalidUnlockSchedule);
+    Ok((tranches, schedule.len() as u8))
+}
+
+// One per normalized symbol (PDA [b"ticker", symbol]); resolves a ticker to its canonical launch.
+#[account]
+#[derive(InitSpace)]
+pub struct TickerRecord {
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    pub launch: Pubkey,             // default => free to claim (unless Reserved)
+    pub mint: Pubkey,
+    pub claimed_at: i64,
+    pub policy: TickerPolicy,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TickerPolicy {
+    ReusableAfterFailure,           // released once its launch fails without graduating
+    Permanent,                      // stays with its launch for good
+    Reserved,                       // only the platform authority can assign it
+}
+
+// Tickers compare case-insensitively: "doge", "DOGE" and " Doge " share one record.
+fn normalize_ticker(symbol: &str) -> String {
+    symbol.trim().to_ascii_uppercase()
+}
+
+fn validate_ticker(ticker: &str) -> Result<()> {
+    require!(
+        !ticker.is_empty() && ticker.len() <= MAX_SYMBOL_LEN && ticker.bytes().all(|b| b.is_ascii_alphanumeric()),
+        LaunchError::InvalidTicker
+    );
+    Ok(())
+}
+
+// Written once by close_and_migrate_to_raydium; no instruction mutates it afterwards.
+#[account]
+#[derive(InitSpace)]
+pub struct GraduationRecord {
+    pub launch: Pubkey,
+    pub mint: Pubkey,
+    pub amm_id: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub token_amount: u64,
+    pub usdc_amount: u64,
+    pub liquidity_burned: bool,
+    pub lock_until: i64,            // 0 when burned
+    pub graduated_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SupplyProof {
+    pub mint: Pubkey,
+    pub supply: u64,                // current, after any burns
+    pub total_supply: u64,
+    pub mint_authority_revoked: bool,
+    pub capped: bool,
+}
+
+const LOCK_DURATION: i64 = 5 * 365 * 24 * 60 * 60;
+const LP_TOKEN_LOCK_ID: u64 = 0;
+const LP_MIGRATION_TIMELOCK: i64 = 48 * 60 * 60;
+const MAX_LP_TRANCHES: usize = 8;
+const MIN_LP_TRANCHE_DELAY: i64 = 365 * 24 * 60 * 60;
+const MAX_LOCK_DURATION: i64 = 10 * 365 * 24 * 60 * 60;
+const USDC_DEVNET: Pubkey = pubkey!("4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU");
+const PLATFORM_WALLET_PUBKEY: Pubkey = pubkey!("7XB2PEWYd5be12CpJ9e4ZTZTHCgNrcTbL7HigciPd1C6");
+const PLATFORM_FEE_USDC: u64 = 5_000_000;          // 5 USDC (6 decimals)
+const BURN_BUY_PCT: u8 = 1;                        // 1%
+const MIN_VIRTUAL_USDC: u64 = 10_000_000;          // 10 USDC
+const PLATFORM_AUTO_TRANSFER_THRESHOLD: u64 = 1_000_000_000; // 1000 USDC
+const ACC_SCALE: u128 = 1_000_000_000_000; // 1e12 scaling for holder index
+const LAUNCH_DURATION: i64 = 72 * 60 * 60;
+const MAX_START_DELAY: i64 = 30 * 24 * 60 * 60;    // schedule at most 30 days ahead
+const MAX_PRESALE_DURATION: i64 = 7 * 24 * 60 * 60;
+const TOKEN_DECIMALS: u8 = 6;
+const MAX_NAME_LEN: usize = 32;
+const MAX_SYMBOL_LEN: usize = 10;
+const MAX_URI_LEN: usize = 200;
+const AUCTION_TICKS: usize = 64;
+const LOTTERY_DRAW_DELAY_SLOTS: u64 = 10;
+const SLOT_HASHES_MAX_ENTRIES: u64 = 512;
+const MAX_COMMIT_REVEAL_WINDOW: i64 = 60 * 60;
+const MAX_BATCH_ORDERS: usize = 24;
+const MAX_ORDER_BATCHES: u64 = 3;                  // settlements an unfilled order may wait through
+const MAX_BATCH_INTERVAL_SLOTS: u64 = 150;         // ~1 minute
+const SLOT_ACTIVITY_LEN: usize = 16;
+const PRICE_SNAPSHOTS: usize = 4;
+const PAUSE_TRADES: u8 = 1 << 0;
+const PAUSE_CLAIMS: u8 = 1 << 1;
+const PAUSE_WITHDRAWALS: u8 = 1 << 2;
+const PAUSE_ALL: u8 = PAUSE_TRADES | PAUSE_CLAIMS | PAUSE_WITHDRAWALS;
+const PRICE_SCALE: u128 = 1_000_000_000_000;
+const MAX_BREAKER_COOLDOWN: i64 = 24 * 60 * 60;
+const MAX_BOND_HOLDING_PERIOD: i64 = 365 * 24 * 60 * 60;
+const MAX_VESTING_BUCKETS: usize = 4;
+const MAX_VESTING_BPS: u64 = 2_000;                // at most 20% of supply
+const MAX_VESTING_DURATION: i64 = 4 * 365 * 24 * 60 * 60;
+// phases in which fee reserves exist and may be claimed or withdrawn
+const POST_START_PHASES: [LaunchPhase; 5] = [
+    LaunchPhase::Curve,
+    LaunchPhase::Succeeded,
+    LaunchPhase::Failed,
+    LaunchPhase::Graduated,
+    LaunchPhase::Archived,
+];
+// every phase before graduation; wind_down fails the launch from any of them
+const WIND_DOWN_PHASES: [LaunchPhase; 7] = [
+    LaunchPhase::Scheduled,
+    LaunchPhase::Presale,
+    LaunchPhase::FixedPriceRound,
+    LaunchPhase::BatchAuction,
+    LaunchPhase::Lottery,
+    LaunchPhase::Curve,
+    LaunchPhase::Succeeded,
+];
+
+fn bonding_curve_buy(usdc_in: u64, virtual_usdc: u64, virtual_token: u64, _decimals: u8) -> u64 {
+    // simple constant-product k = virtual_usdc * virtual_token
+    let new_usdc = virtual_usdc.saturating_add(usdc_in);
+    let new_token = (virtual_token as u128)
+        .saturating_mul(virtual_usdc as u128)
+        .checked_div(new_usdc as u128)
+        .unwrap_or(0) as u64;
+    virtual_token.saturating_sub(new_token)
+}
+
+fn bonding_curve_sell(token_in: u64, virtual_usdc: u64, virtual_token: u64, _decimals: u8) -> u64 {
+    let new_token = virtual_token.saturating_add(token_in);
+    let new_usdc = (virtual_usdc as u128)
+        .saturating_mul(virtual_token as u128)
+        .checked_div(new_token as u128)
+        .unwrap_or(0) as u64;
+    virtual_usdc.saturating_sub(new_usdc)
+}
+
+// ---- weighted (LBP) math, 1e18 fixed point ----
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+const LN2_WAD: u128 = 693_147_180_559_945_309;
+const LBP_MIN_WEIGHT_BPS: u16 = 100;
+const LBP_MAX_WEIGHT_BPS: u16 = 9_900;
+
+// log2(x / WAD) scaled by WAD, for x >= WAD
+fn log2_wad(mut x: u128) -> u128 {
+    let mut res = 0u128;
+    while x >= 2 * WAD {
+        x /= 2;
+        res += WAD;
+    }
+    let mut delta = WAD / 2;
+    while delta > 0 {
+        x = x * x / WAD;
+        if x >= 2 * WAD {
+            x /= 2;
+            res += delta;
+        }
+        delta /= 2;
+    }
+    res
+}
+
+// 2^(-y / WAD) scaled by WAD
+fn exp2_neg_wad(y: u128) -> u128 {
+    let int = y / WAD;
+    if int >= 64 {
+        return 0;
+    }
+    // e^(-z) with z = frac * ln2 < 0.7, Taylor series
+    let z = (y % WAD) * LN2_WAD / WAD;
+    let mut term = WAD as i128;
+    let mut sum = WAD as i128;
+    for n in 1..24i128 {
+        term = -term * z as i128 / (n * WAD as i128);
+        sum += term;
+        if term == 0 {
+            break;
+        }
+    }
+    (sum.max(0) as u128) >> int
+}
+
+// (balance / (balance + amount_in)) ^ (weight_in / weight_out), scaled by WAD
+fn weighted_factor(balance: u64, amount_in: u64, weight_in: u16, weight_out: u16) -> u128 {
+    if balance == 0 || weight_out == 0 {
+        return 0;
+    }
+    let ratio = (balance as u128 + amount_in as u128) * WAD / balance as u128;
+    let exponent = weight_in as u128 * WAD / weight_out as u128;
+    exp2_neg_wad(exponent.saturating_mul(log2_wad(ratio)) / WAD)
+}
+
+fn lbp_buy(usdc_in: u64, virtual_usdc: u64, virtual_token: u64, token_weight_bps: u16) -> u64 {
+    let usdc_weight = 10_000u16.saturating_sub(token_weight_bps);
+    let factor = weighted_factor(virtual_usdc, usdc_in, usdc_weight, token_weight_bps);
+    ((virtual_token as u128) * WAD.saturating_sub(factor) / WAD) as u64
+}
+
+fn lbp_sell(token_in: u64, virtual_usdc: u64, virtual_token: u64, token_weight_bps: u16) -> u64 {
+    let usdc_weight = 10_000u16.saturating_sub(token_weight_bps);
+    let factor = weighted_factor(virtual_token, token_in, token_weight_bps, usdc_weight);
+    ((virtual_usdc as u128) * WAD.saturating_sub(factor) / WAD) as u64
+}
+
+// Virtual token reserve that opens the curve at `price` once the round's USDC is added. Round
+// buyers paid `price` per token rounded up, so their USDC backs `tokens_sold` at that price.
+fn round_seed_tokens(virtual_usdc: u64, price: u64, tokens_sold: u64) -> u128 {
+    (virtual_usdc as u128)
+        .saturating_mul(10u128.pow(TOKEN_DECIMALS as u32))
+        .checked_div(price as u128)
+        .unwrap_or(0)
+        .saturating_add(tokens_sold as u128)
+}
+
+// dynamic tax tiers by trade size vs total supply
+fn sell_tax(token_amount: u64, usdc_out: u64, total_supply: u64) -> u64 {
+    let sell_percentage = (token_amount as u128)
+        .saturating_mul(100)
+        .checked_div(total_supply as u128)
+        .unwrap_or(0) as u64;
+    let tax_rate = match sell_percentage {
+        0..=30 => 5,      // 0.5 %
+        31..=70 => 10,    // 1 %
+        _ => 30,          // 3 %
+    };
+    (usdc_out as u128)
+        .saturating_mul(tax_rate as u128)
+        .checked_div(1000)
+        .unwrap_or(0) as u64
+}
+
+fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_NAME_LEN && !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LEN,
+        LaunchError::InvalidMetadata
+    );
+    require!(uri.len() <= MAX_URI_LEN, LaunchError::InvalidMetadata);
+    Ok(())
+}
+
+// Token-2022 reallocs the mint for metadata but leaves rent to the caller: top it up first.
+fn fund_metadata_growth<'info>(
+    payer: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    extra_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(mint.data_len() + extra_len);
+    let shortfall = required.saturating_sub(mint.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(system_program, anchor_lang::system_program::Transfer { from: payer, to: mint }),
+            shortfall,
+        )?;
+    }
+    Ok(())
+}
+
+// tokens outside the launch's own curve inventory
+// per-token share of `amount` spread over `supply`, scaled by ACC_SCALE
+fn index_increment(amount: u64, supply: u64) -> u128 {
+    if supply == 0 {
+        return 0;
+    }
+    (amount as u128).saturating_mul(ACC_SCALE) / supply as u128
+}
+
+fn circulating_supply(mint: &Mint, curve_ata: &TokenAccount) -> u64 {
+    mint.supply.saturating_sub(curve_ata.amount)
+}
+
+// pays `amount` out of the curve inventory once the supply is finalized, otherwise mints it
+#[allow(clippy::too_many_arguments)]
+fn issue_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    mint_auth: AccountInfo<'info>,
+    mint_auth_bump: u8,
+    curve_ata: AccountInfo<'info>,
+    launch_config: AccountInfo<'info>,
+    launch_bump: u8,
+    supply_finalized: bool,
+    amount: u64,
+) -> Result<()> {
+    let mint_key = mint.key();
+    if supply_finalized {
+        let seeds: &[&[u8]] = &[b"launch", mint_key.as_ref(), &[launch_bump]];
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                Transfer { from: curve_ata, to, authority: launch_config },
+                &[seeds],
+            ),
+            amount,
+        )
+    } else {
+        let seeds: &[&[u8]] = &[b"mint-auth", mint_key.as_ref(), &[mint_auth_bump]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program,
+                MintTo { mint, to, authority: mint_auth },
+                &[seeds],
+            ),
+            amount,
+        )
+    }
+}
+
+// takes sold tokens back: into the curve inventory once the supply is finalized, otherwise burned
+#[allow(clippy::too_many_arguments)]
+fn return_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    curve_ata: AccountInfo<'info>,
+    supply_finalized: bool,
+    amount: u64,
+) -> Result<()> {
+    if supply_finalized {
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                Transfer { from, to: curve_ata, authority },
+                signer_seeds,
+            ),
+            amount,
+        )
+    } else {
+        token_interface::burn(
+            CpiContext::new_with_signer(token_program, Burn { mint, from, authority }, signer_seeds),
+            amount,
+        )
+    }
+}
+
+fn burn_inventory<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    curve_ata: AccountInfo<'info>,
+    launch_config: AccountInfo<'info>,
+    launch_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let seeds: &[&[u8]] = &[b"launch", mint_key.as_ref(), &[launch_bump]];
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            token_program,
+            Burn { mint, from: curve_ata, authority: launch_config },
+            &[seeds],
+        ),
+        amount,
+    )
+}
+
+// Whether `dest` can take `mint` right now. A closed, frozen or memo-required account would make
+// the payout CPI fail and abort the whole batch with it.
+fn can_receive(dest: &AccountInfo, token_program: &Pubkey, mint: &Pubkey) -> bool {
+    if dest.owner != token_program {
+        return false;
+    }
+    let Ok(data) = dest.try_borrow_data() else {
+        return false;
+    };
+    let Ok(account) = StateWithExtensions::<TokenAccountState>::unpack(&data) else {
+        return false;
+    };
+    let memo_required = account
+        .get_extension::<MemoTransfer>()
+        .is_ok_and(|m| bool::from(m.require_incoming_transfer_memos));
+    account.base.mint == *mint && account.base.state == AccountState::Initialized && !memo_required
+}
+
+// loads the order owner's buyer ledger from remaining_accounts, applies `update` and writes it back
+fn update_order_ledger<'info>(
+    ledger_ai: &'info AccountInfo<'info>,
+    mint: &Pubkey,
+    order: &BatchOrder,
+    update: impl FnOnce(&mut BuyerLedger),
+) -> Result<()> {
+    let mut ledger: Account<'info, BuyerLedger> = Account::try_from(ledger_ai)?;
+    let expected = Pubkey::create_program_address(
+        &[b"buyer_ledger", mint.as_ref(), order.owner.as_ref(), &[ledger.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| LaunchError::NotYourLedger)?;
+    require!(
+        ledger_ai.key() == expected && ledger.buyer == order.owner,
+        LaunchError::NotYourLedger
+    );
+    update(&mut ledger);
+    ledger.exit(&crate::ID)
+}
+
+// leaves an order's escrow where it is and credits it to the owner's ledger for claim_batch_refund
+fn credit_batch_refund<'info>(ledger_ai: &'info AccountInfo<'info>, mint: &Pubkey, order: &BatchOrder) -> Result<()> {
+    update_order_ledger(ledger_ai, mint, order, |ledger| {
+        if order.is_buy {
+            ledger.batch_refund_usdc = ledger.batch_refund_usdc.saturating_add(order.amount_in);
+        } else {
+            ledger.batch_refund_tokens = ledger.batch_refund_tokens.saturating_add(order.amount_in);
+        }
+    })
+}
+
+// counts a filled order towards the bond limit like a curve trade: `tokens` received or sold
+fn record_batch_fill<'info>(
+    ledger_ai: &'info AccountInfo<'info>,
+    mint: &Pubkey,
+    order: &BatchOrder,
+    tokens: u64,
+) -> Result<()> {
+    update_order_ledger(ledger_ai, mint, order, |ledger| {
+        if order.is_buy {
+            ledger.tokens_bought = ledger.tokens_bought.saturating_add(tokens);
+        } else {
+            ledger.tokens_sold = ledger.tokens_sold.saturating_add(tokens);
+        }
+    })
+}
+
+// issues the buyer's share of `tokens_out` and burns the BURN_BUY_PCT fee; returns (user, burned)
+#[allow(clippy::too_many_arguments)]
+fn issue_buy_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    buyer_ata: AccountInfo<'info>,
+    burn_ata: AccountInfo<'info>,
+    mint_auth: AccountInfo<'info>,
+    mint_auth_bump: u8,
+    curve_ata: AccountInfo<'info>,
+    launch_config: AccountInfo<'info>,
+    launch_bump: u8,
+    supply_finalized: bool,
+    tokens_out: u64,
+) -> Result<(u64, u64)> {
+    let burn_amount = tokens_out.saturating_mul(BURN_BUY_PCT as u64) / 100;
+    let user_tokens = tokens_out.saturating_sub(burn_amount);
+
+    issue_tokens(
+        token_program.clone(),
+        mint.clone(),
+        buyer_ata,
+        mint_auth.clone(),
+        mint_auth_bump,
+        curve_ata.clone(),
+        launch_config.clone(),
+        launch_bump,
+        supply_finalized,
+        user_tokens,
+    )?;
+
+    if burn_amount > 0 {
+        if supply_finalized {
+            burn_inventory(token_program, mint, curve_ata, launch_config, launch_bump, burn_amount)?;
+        } else {
+            // mint and burn for fee
+            let mint_key = mint.key();
+            let seeds: &[&[u8]] = &[b"mint-auth", mint_key.as_ref(), &[mint_auth_bump]];
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    MintTo { mint: mint.clone(), to: burn_ata.clone(), authority: mint_auth.clone() },
+                    &[seeds],
+                ),
+                burn_amount,
+            )?;
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    token_program,
+                    Burn { mint, from: burn_ata, authority: mint_auth },
+                    &[seeds],
+                ),
+                burn_amount,
+            )?;
+        }
+    }
+    Ok((user_tokens, burn_amount))
+}
+
+// Rejects a transaction that both buys and sells `launch`, or that reaches buy/sell through
+// a CPI wrapper (which would hide the other leg from the Instructions sysvar).
+fn check_sandwich(ix_sysvar: &AccountInfo, launch: &Pubkey) -> Result<()> {
+    let current = load_current_index_checked(ix_sysvar)? as usize;
+    let this = load_instruction_at_checked(current, ix_sysvar)?;
+    require!(this.program_id == crate::ID, LaunchError::SandwichDetected);
+
+    let (mut buys, mut sells) = (0u32, 0u32);
+    let mut i = 0;
+    while let Ok(ix) = load_instruction_at_checked(i, ix_sysvar) {
+        if ix.program_id == crate::ID && ix.accounts.iter().any(|m| m.pubkey == *launch) {
+            if ix.data.starts_with(instruction::Buy::DISCRIMINATOR) {
+                buys += 1;
+            } else if ix.data.starts_with(instruction::Sell::DISCRIMINATOR) {
+                sells += 1;
+            }
+        }
+        i += 1;
+    }
+    require!(buys == 0 || sells == 0, LaunchError::SandwichDetected);
+    Ok(())
+}
+
+fn enforce_sandwich_guard(
+    ix_sysvar: Option<&UncheckedAccount>,
+    activity: Option<&mut Box<Account<SlotActivity>>>,
+    activity_bump: Option<u8>,
+    launch: Pubkey,
+    trader: Pubkey,
+    is_buy: bool,
+) -> Result<()> {
+    let (Some(ix_sysvar), Some(activity)) = (ix_sysvar, activity) else {
+        return err!(LaunchError::GuardAccountsMissing);
+    };
+    check_sandwich(ix_sysvar, &launch)?;
+    if activity.launch == Pubkey::default() {
+        activity.launch = launch;
+        activity.bump = activity_bump.unwrap_or_default();
+    }
+    activity.record(trader, Clock::get()?.slot, is_buy)
+}
+
+// leaf = keccak(buyer || allocation_usdc le), sorted-pair hashing up the tree
+fn verify_allowlist(root: &[u8; 32], buyer: &Pubkey, allocation_usdc: u64, proof: &[[u8; 32]]) -> bool {
+    let mut node = keccak::hashv(&[buyer.as_ref(), &allocation_usdc.to_le_bytes()]).0;
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        };
+    }
+    node == *root
+}
+
+// SlotHashes layout: u64 len, then (slot u64, hash [u8; 32]) entries, newest first
+fn slot_hash_at(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    let len = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    for i in 0..len {
+        let off = 8 + i * 40;
+        let entry = data.get(off..off + 40)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().ok()?);
+        if entry_slot == slot {
+            return entry[8..].try_into().ok();
+        }
+        if entry_slot < slot {
+            return None;
+        }
+    }
+    None
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[derive(PartialEq)]
+pub enum CallerType {
+    Platform,
+    Creator,
+    Holder,
+}
+
+fn classify_caller(
+    caller: &Pubkey,
+    launch_config: &LaunchConfig,
+    _ledger: Option<&BuyerLedger>,
+) -> Result<CallerType> {
+    if *caller == PLATFORM_WALLET_PUBKEY {
+        return Ok(CallerType::Platform);
+    }
+    if *caller == launch_config.creator {
+        return Ok(CallerType::Creator);
+    }
+    Ok(CallerType::Holder)
+}
+
+#[program]
+pub mod lumen_launch {
+    use super::*;
+
+    pub fn create_token(
+        ctx: Context<CreateToken>,
+        _usdc_mint: Pubkey,
+        virtual_usdc_amount: u64,
+        params: LaunchParams,
+    ) -> Result<()> {
+        require!(virtual_usdc_amount >= MIN_VIRTUAL_USDC, LaunchError::Min10USDC);
+        let metadata = &params.metadata;
+        validate_metadata(&metadata.name, &metadata.symbol, &metadata.uri)?;
+        let ticker = normalize_ticker(&metadata.symbol);
+        validate_ticker(&ticker)?;
+        let now = Clock::get()?.unix_timestamp;
+        let start_time = params.start_time.unwrap_or(now);
+        require!(
+            start_time >= now && start_time - now <= MAX_START_DELAY,
+            LaunchError::InvalidStartTime
+        );
+        let (merkle_root, presale_duration) = match &params.presale {
+            Some(p) => {
+                require!(
+                    p.merkle_root != [0u8; 32] && p.duration > 0 && p.duration <= MAX_PRESALE_DURATION,
+                    LaunchError::InvalidPresale
+                );
+                (p.merkle_root, p.duration)
+            }
+            None => ([0u8; 32], 0),
+        };
+        let modes = [
+            params.presale.is_some(),
+            params.fixed_price_round.is_some(),
+            params.batch_auction.is_some(),
+            params.lottery.is_some(),
+        ];
+        require!(modes.iter().filter(|m| **m).count() <= 1, LaunchError::ConflictingModes);
+        if let Some(c) = &params.commit_reveal {
+            require!(
+                c.commit_window > 0
+                    && c.commit_window <= MAX_COMMIT_REVEAL_WINDOW
+                    && c.reveal_window > 0
+                    && c.reveal_window <= MAX_COMMIT_REVEAL_WINDOW,
+                LaunchError::InvalidCommitReveal
+            );
+        }
+        if let Some(b) = &params.batch_trading {
+            require!(
+                b.interval_slots > 0 && b.interval_slots <= MAX_BATCH_INTERVAL_SLOTS,
+                LaunchError::InvalidBatchTrading
+            );
+        }
+        if let Some(b) = &params.creator_bond {
+            require!(
+                b.amount > 0
+                    && b.max_sell_bps <= 10_000
+                    && (0..=MAX_BOND_HOLDING_PERIOD).contains(&b.holding_period),
+                LaunchError::InvalidBond
+            );
+        }
+        if let Some(b) = &params.circuit_breaker {
+            require!(
+                b.drop_bps > 0
+                    && b.drop_bps < 10_000
+                    && b.window_slots > 0
+                    && b.cooldown_secs > 0
+                    && b.cooldown_secs <= MAX_BREAKER_COOLDOWN,
+                LaunchError::InvalidBreaker
+            );
+        }
+        if let Some(w) = &params.lbp {
+            // the opening modes below price against the constant-product curve
+            require!(
+                params.fixed_price_round.is_none()
+                    && params.batch_auction.is_none()
+                    && params.lottery.is_none()
+                    && params.batch_trading.is_none(),
+                LaunchError::ConflictingModes
+            );
+            let range = LBP_MIN_WEIGHT_BPS..=LBP_MAX_WEIGHT_BPS;
+            require!(
+                range.contains(&w.start_weight_bps) && range.contains(&w.end_weight_bps),
+                LaunchError::InvalidWeights
+            );
+        }
+        if let Some(l) = &params.lottery {
+            require!(
+                l.ticket_usdc > 0 && l.max_winners > 0 && l.duration > 0 && l.duration <= MAX_PRESALE_DURATION,
+                LaunchError::InvalidLottery
+            );
+        }
+        if let Some(a) = &params.batch_auction {
+            require!(
+                a.tick_size > 0 && a.duration > 0 && a.duration <= MAX_PRESALE_DURATION,
+                LaunchError::InvalidAuction
+            );
+        }
+        if let Some(r) = &params.fixed_price_round {
+            require!(
+                r.price > 0
+                    && r.max_tokens > 0
+                    && r.wallet_cap_tokens > 0
+                    && r.duration > 0
+                    && r.duration <= MAX_PRESALE_DURATION,
+                LaunchError::InvalidRound
+            );
+        }
+
+        // 1) Collect fixed platform fee to platform USDC ATA
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_usdc.to_account_info(),
+                    to: ctx.accounts.platform_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            PLATFORM_FEE_USDC,
+        )?;
+
+        // 2) Seed USDC vault with virtual liquidity (kept in vault)
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_usdc.to_account_info(),
+                    to: ctx.accounts.usdc_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            virtual_usdc_amount,
+        )?;
+
+        let config = &mut ctx.accounts.launch_config;
+        let decimals = ctx.accounts.usdc_mint.decimals; // USDC decimals
+        let supply = 1_000_000_000u64
+            .checked_mul(10u64.pow(decimals as u32))
+            .ok_or(LaunchError::Overflow)?;
+
+        config.total_supply = supply;
+        config.virtual_usdc = virtual_usdc_amount;
+        config.virtual_token = supply / 2;
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+        config.start_time = start_time;
+        config.merkle_root = merkle_root;
+        config.curve_start_time = start_time + presale_duration;
+        match &params.fixed_price_round {
+            Some(r) => {
+                require!(r.max_tokens <= supply / 2, LaunchError::InvalidRound);
+                // a sold-out round must still be able to open the curve at the round price
+                require!(
+                    round_seed_tokens(virtual_usdc_amount, r.price, r.max_tokens) <= supply as u128,
+                    LaunchError::InvalidRound
+                );
+                config.round_price = r.price;
+                config.round_max_tokens = r.max_tokens;
+                config.round_wallet_cap = r.wallet_cap_tokens;
+                config.round_soft_cap = r.soft_cap_usdc;
+                config.curve_start_time = start_time + r.duration;
+            }
+            None => config.round_max_tokens = 0,
+        }
+        config.round_tokens_sold = 0;
+        config.round_raised = 0;
+        match &params.batch_auction {
+            Some(a) => {
+                config.auction_min_price = a.min_price;
+                config.auction_tick_size = a.tick_size;
+                config.curve_start_time = start_time + a.duration;
+            }
+            None => config.auction_tick_size = 0,
+        }
+        config.auction_clearing_price = 0;
+        config.auction_marginal_tick = 0;
+        config.auction_marginal_demand = 0;
+        config.auction_marginal_fill = 0;
+        config.auction_quote_filled = 0;
+        config.auction_tokens_filled = 0;
+        match &params.lottery {
+            Some(l) => {
+                config.lottery_ticket_usdc = l.ticket_usdc;
+                config.lottery_max_winners = l.max_winners;
+                config.curve_start_time = start_time + l.duration;
+            }
+            None => config.lottery_ticket_usdc = 0,
+        }
+        config.lottery_entries = 0;
+        config.lottery_draw_slot = 0;
+        config.lottery_seed = [0u8; 32];
+        config.lottery_perm_a = 0;
+        config.lottery_perm_b = 0;
+        config.lottery_tokens_per_ticket = 0;
+        config.batch_interval_slots = params.batch_trading.as_ref().map_or(0, |b| b.interval_slots);
+        config.sandwich_guard = params.sandwich_guard;
+        match &params.circuit_breaker {
+            Some(b) => {
+                config.breaker_drop_bps = b.drop_bps;
+                config.breaker_window_slots = b.window_slots;
+                config.breaker_cooldown = b.cooldown_secs;
+            }
+            None => config.breaker_drop_bps = 0,
+        }
+        config.halted_until = 0;
+        config.snapshot_cursor = 0;
+        config.price_snapshots = [PriceSnapshot::default(); PRICE_SNAPSHOTS];
+        config.paused_scopes = 0;
+        config.wound_down = false;
+        match &params.creator_bond {
+            Some(b) => {
+                config.bond_amount = b.amount;
+                config.bond_in_launch_token = b.in_launch_token;
+                config.bond_max_sell_bps = b.max_sell_bps;
+                config.bond_holding_period = b.holding_period;
+            }
+            None => config.bond_amount = 0,
+        }
+        config.bond_posted = false;
+        config.bond_settled = false;
+        config.graduated_at = 0;
+        let mut vesting_total = 0u64;
+        require!(params.vesting.len() <= MAX_VESTING_BUCKETS, LaunchError::InvalidVesting);
+        config.vesting_buckets = [VestingBucket::default(); MAX_VESTING_BUCKETS];
+        for (i, v) in params.vesting.iter().enumerate() {
+            require!(
+                v.beneficiary != Pubkey::default()
+                    && v.amount > 0
+                    && v.cliff_secs >= 0
+                    && v.duration_secs >= v.cliff_secs
+                    && v.duration_secs <= MAX_VESTING_DURATION,
+                LaunchError::InvalidVesting
+            );
+            vesting_total = vesting_total.checked_add(v.amount).ok_or(LaunchError::Overflow)?;
+            config.vesting_buckets[i] = VestingBucket {
+                beneficiary: v.beneficiary,
+                amount: v.amount,
+                claimed: 0,
+                cliff_secs: v.cliff_secs,
+                duration_secs: v.duration_secs,
+            };
+        }
+        require!(
+            vesting_total as u128 <= (supply as u128) * (MAX_VESTING_BPS as u128) / 10_000,
+            LaunchError::InvalidVesting
+        );
+        config.vesting_total = vesting_total;
+        config.vesting_claimed = 0;
+        match &params.commit_reveal {
+            Some(c) => {
+                config.commit_window = c.commit_window;
+                config.reveal_window = c.reveal_window;
+            }
+            None => {
+                config.commit_window = 0;
+                config.reveal_window = 0;
+            }
+        }
+        match &params.lbp {
+            Some(w) => {
+                config.lbp_start_weight_bps = w.start_weight_bps;
+                config.lbp_end_weight_bps = w.end_weight_bps;
+            }
+            None => {
+                config.lbp_start_weight_bps = 0;
+                config.lbp_end_weight_bps = 0;
+            }
+        }
+        config.deadline = config.curve_start_time + LAUNCH_DURATION;
+        config.anti_snipe_blocks = 2;
+        config.snipe_max_pct = 10; // 0.1% of supply as default (in 1/10,000 => 10)
+        config.snipe_slippage = 50;
+        config.platform_wallet = PLATFORM_WALLET_PUBKEY;
+        config.creator = ctx.accounts.creator.key();
+        config.bump = ctx.bumps.launch_config;
+        config.usdc_decimals = decimals;
+        config.phase = LaunchPhase::Scheduled;
+        config.creator_paid_usdc = virtual_usdc_amount;
+        config.platform_fees_collected = PLATFORM_FEE_USDC;
+        config.platform_auto_transferred = 0;
+        config.last_withdraw = 0;
+        config.in_trade = false;
+        config.auto_withdraw_threshold = PLATFORM_AUTO_TRANSFER_THRESHOLD;
+        config.creator_reserve_usdc = 0;
+        config.holders_reserve_usdc = 0;
+        config.holders_index = 0;
+        config.holders_reserve_tokens = 0;
+        config.holders_token_index = 0;
+
+        let launch = config.key();
+        emit!(LaunchScheduledEvent {
+            launch,
+            mint: ctx.accounts.mint.key(),
+            creator: config.creator,
+            start_time: config.start_time,
+            deadline: config.deadline,
+        });
+        config.sync_phase(launch, now)?;
+
+        // claim the ticker; a record left free by a failed launch is taken over as-is
+        let record = &mut ctx.accounts.ticker_record;
+        require!(
+            record.launch == Pubkey::default() && record.policy != TickerPolicy::Reserved,
+            LaunchError::TickerTaken
+        );
+        record.symbol = ticker;
+        record.launch = launch;
+        record.mint = ctx.accounts.mint.key();
+        record.claimed_at = now;
+        record.bump = ctx.bumps.ticker_record;
+        emit!(TickerClaimedEvent { symbol: record.symbol.clone(), launch, mint: record.mint });
+
+        // 3) Name, symbol and URI live on the mint itself; mint_auth may edit them until graduation
+        let mint_key = ctx.accounts.mint.key();
+        let mint_seeds: &[&[u8]] = &[b"mint-auth", mint_key.as_ref(), &[ctx.bumps.mint_auth]];
+        let token_metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(ctx.accounts.mint_auth.key()),
+            mint: mint_key,
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            ..Default::default()
+        };
+        fund_metadata_growth(
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            token_metadata.tlv_size_of()?,
+        )?;
+        token_interface::token_metadata_initialize(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                TokenMetadataInitialize {
+                    program_id: ctx.accounts.token_2022_program.to_account_info(),
+                    metadata: ctx.accounts.mint.to_account_info(),
+                    update_authority: ctx.accounts.mint_auth.to_account_info(),
+                    mint_authority: ctx.accounts.mint_auth.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+                &[mint_seeds],
+            ),
+            token_metadata.name,
+            token_metadata.symbol,
+            token_metadata.uri,
+        )?;
+
+        // 4) Mint vesting allocations into the vesting vault
+        if vesting_total > 0 {
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_2022_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.vesting_vault.to_account_info(),
+                        authority: ctx.accounts.mint_auth.to_account_info(),
+                    },
+                    &[mint_seeds],
+                ),
+                vesting_total,
+            )?;
+            for (i, v) in params.vesting.iter().enumerate() {
+                emit!(VestingCreatedEvent {
+                    launch,
+                    index: i as u8,
+                    beneficiary: v.beneficiary,
+                    amount: v.amount,
+                    cliff_secs: v.cliff_secs,
+                    duration_secs: v.duration_secs,
+                });
+            }
+        }
+
+        // 5) Fixed supply: everything else goes to the curve inventory and the mint is closed for good
+        if params.fixed_supply {
+            let inventory = supply.saturating_sub(vesting_total);
+            token_interface::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_2022_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.bonding_curve_ata.to_account_info(),
+                        authority: ctx.accounts.mint_auth.to_account_info(),
+                    },
+                    &[mint_seeds],
+                ),
+                inventory,
+            )?;
+            token_interface::set_authority(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_2022_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: ctx.accounts.mint_auth.to_account_info(),
+                        account_or_mint: ctx.accounts.mint.to_account_info(),
+                    },
+                    &[mint_seeds],
+                ),
+                AuthorityType::MintTokens,
+                None,
+            )?;
+            ctx.accounts.launch_config.supply_finalized = true;
+            emit!(SupplyFinalized {
+                launch,
+                mint: mint_key,
+                supply,
+                minted: inventory,
+                liquidity_minted: inventory,
+                claims_reserved: 0,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn init_platform_config(ctx: Context<InitPlatformConfig>, guardian: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform_config;
+        platform.authority = ctx.accounts.platform.key();
+        platform.guardian = guardian;
+        platform.paused_scopes = 0;
+        platform.bump = ctx.bumps.platform_config;
+        platform.amm_adapter = Pubkey::default();
+        platform.lp_fee_owner_bps = 3_000; // same 30/50/20 split as the sell tax
+        platform.lp_fee_holders_bps = 5_000;
+        emit!(GuardianUpdatedEvent { previous: Pubkey::default(), guardian });
+        Ok(())
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform_config;
+        let previous = platform.guardian;
+        platform.guardian = guardian;
+        emit!(GuardianUpdatedEvent { previous, guardian });
+        Ok(())
+    }
+
+    pub fn set_platform_pause(ctx: Context<SetPlatformPause>, scopes: u8) -> Result<()> {
+        require!(scopes & !PAUSE_ALL == 0, LaunchError::InvalidPauseScope);
+        let platform = &mut ctx.accounts.platform_config;
+        let previous_scopes = platform.paused_scopes;
+        platform.paused_scopes = scopes;
+        emit!(PauseUpdatedEvent {
+            launch: None,
+            guardian: ctx.accounts.guardian.key(),
+            previous_scopes,
+            scopes,
+        });
+        Ok(())
+    }
+
+    pub fn set_launch_pause(ctx: Context<SetLaunchPause>, scopes: u8) -> Result<()> {
+        require!(scopes & !PAUSE_ALL == 0, LaunchError::InvalidPauseScope);
+        let config = &mut ctx.accounts.launch_config;
+        let previous_scopes = config.paused_scopes;
+        config.paused_scopes = scopes;
+        emit!(PauseUpdatedEvent {
+            launch: Some(config.key()),
+            guardian: ctx.accounts.guardian.key(),
+            previous_scopes,
+            scopes,
+        });
+        Ok(())
+    }
+
+    // Kill switch for scam launches: trading is frozen, the launch is failed and every USDC
+    // owed to the creator joins the pool that holders redeem against pro rata.
+    pub fn wind_down(ctx: Context<WindDown>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&WIND_DOWN_PHASES)?;
+
+        let previous_scopes = config.paused_scopes;
+        config.paused_scopes |= PAUSE_TRADES;
+        config.wound_down = true;
+        let forfeited = config.creator_reserve_usdc.saturating_add(config.creator_paid_usdc);
+        config.creator_reserve_usdc = 0;
+        config.creator_paid_usdc = 0;
+        config.holders_reserve_usdc = 0; // folded into the redemption pool
+        config.void_opening_sale();
+        config.transition(launch, LaunchPhase::Failed)?;
+
+        let guardian = ctx.accounts.guardian.key();
+        emit!(PauseUpdatedEvent {
+            launch: Some(launch),
+            guardian,
+            previous_scopes,
+            scopes: config.paused_scopes,
+        });
+        emit!(WoundDownEvent { launch, guardian, forfeited_creator_usdc: forfeited, timestamp: now });
+        Ok(())
+    }
+
+    // Burns `amount` tokens for amount / supply of the vault left after platform fees and round
+    // refunds. Unclaimed round, auction and lottery allocations count towards the supply, so
+    // their holders get the same share whether they claim and redeem early or late.
+    pub fn redeem(ctx: Context<Redeem>, amount: u64) -> Result<()> {
+        require!(amount > 0, LaunchError::ZeroAmount);
+        let config = &ctx.accounts.launch_config;
+        require!(config.wound_down, LaunchError::NotWoundDown);
+
+        let reserved_platform = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
+        let round_refunds = if config.round_succeeded() { 0 } else { config.round_raised };
+        let pool = ctx
+            .accounts
+            .usdc_vault
+            .amount
+            .saturating_sub(reserved_platform)
+            .saturating_sub(round_refunds);
+        let locked_vesting = config.vesting_total.saturating_sub(config.vesting_claimed);
+        let supply = circulating_supply(&ctx.accounts.mint, &ctx.accounts.bonding_curve_ata)
+            .saturating_sub(locked_vesting)
+            .saturating_add(config.presale_owed());
+        let usdc_out = (amount as u128)
+            .saturating_mul(pool as u128)
+            .checked_div(supply as u128)
+            .unwrap_or(0) as u64;
+        require!(usdc_out > 0, LaunchError::ZeroEntitled);
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_2022_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_x_ata.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.holder_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            usdc_out,
+        )?;
+
+        emit!(RedeemEvent { holder: ctx.accounts.holder.key(), tokens_burned: amount, usdc_out });
+        Ok(())
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>, index: u8) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)?;
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        // a wound-down launch forfeits whatever is still locked
+        require!(!config.wound_down, LaunchError::WrongPhase);
+        let start_time = config.start_time;
+        let bucket = config
+            .vesting_buckets
+            .get_mut(index as usize)
+            .ok_or(LaunchError::InvalidVesting)?;
+        require!(bucket.beneficiary == ctx.accounts.beneficiary.key(), LaunchError::NotYourLedger);
+        let amount = bucket.vested(start_time, now).saturating_sub(bucket.claimed);
+        require!(amount > 0, LaunchError::ZeroEntitled);
+        bucket.claimed = bucket.claimed.saturating_add(amount);
+        config.vesting_claimed = config.vesting_claimed.saturating_add(amount);
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_x_ata.to_account_info(),
+                    authority: ctx.accounts.vesting_auth.to_account_info(),
+                },
+                &[&[b"vesting", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.vesting_auth]]],
+            ),
+            amount,
+        )?;
+
+        emit!(VestingClaimedEvent {
+            launch: config.key(),
+            index,
+            beneficiary: ctx.accounts.beneficiary.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    pub fn post_bond(ctx: Context<PostBond>) -> Result<()> {
+        let config = &mut ctx.accounts.launch_config;
+        require!(config.has_bond(), LaunchError::InvalidBond);
+        require!(!config.bond_posted, LaunchError::BondSettled);
+        config.require_phase(&[LaunchPhase::Scheduled, LaunchPhase::Presale, LaunchPhase::Curve])?;
+        config.bond_posted = true;
+
+        let amount = config.bond_amount;
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.bond_token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_bond_ata.to_account_info(),
+                    to: ctx.accounts.bond_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        emit!(BondPostedEvent {
+            launch: config.key(),
+            bond_mint: ctx.accounts.bond_mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    // Permissionless: anyone holding proof (the creator's ledger) can slash. A USDC bond
+    // moves into the vault as holders reserve; a token bond is sold into the curve while it
+    // is live and the proceeds go to holders, otherwise (or when the sale would trip the
+    // circuit breaker) it is burned.
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        require!(config.bond_posted && !config.bond_settled, LaunchError::BondSettled);
+        require!(config.bond_violated(&ctx.accounts.creator_ledger), LaunchError::BondNotSlashable);
+        config.bond_settled = true;
+
+        let amount = ctx.accounts.bond_vault.amount;
+        let mint_key = ctx.accounts.mint.key();
+        let bond_seeds: &[&[u8]] = &[b"bond-vault", mint_key.as_ref(), &[ctx.bumps.bond_vault_auth]];
+        let mut holders_usdc = 0u64;
+        if !config.bond_in_launch_token {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.bond_token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.bond_vault.to_account_info(),
+                        to: ctx.accounts.usdc_vault.to_account_info(),
+                        authority: ctx.accounts.bond_vault_auth.to_account_info(),
+                    },
+                    &[bond_seeds],
+                ),
+                amount,
+            )?;
+            // after a wind_down the USDC simply joins the redemption pool
+            if !config.wound_down {
+                holders_usdc = amount;
+            }
+        } else {
+            require!(!config.in_trade, LaunchError::Reentrancy);
+            if !config.wound_down && matches!(config.phase, LaunchPhase::Curve | LaunchPhase::Succeeded) {
+                // sold like any other sell, except that one the breaker would stop is burned instead
+                let slot = Clock::get()?.slot;
+                let usdc_out = config.curve_sell(amount, now);
+                let price_after = config.price_at(
+                    config.virtual_usdc.saturating_sub(usdc_out),
+                    config.virtual_token.saturating_add(amount),
+                    now,
+                );
+                if !config.breaker_halted(now) && config.check_breaker(price_after, now, slot).is_ok() {
+                    holders_usdc = usdc_out;
+                    config.virtual_usdc = config.virtual_usdc.saturating_sub(holders_usdc);
+                    config.virtual_token = config.virtual_token.saturating_add(amount);
+                    config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+                    let launch = config.key();
+                    config.update_breaker(launch, now, slot)?;
+                }
+            }
+            // tokens sold into the curve back its inventory; otherwise they are destroyed
+            return_tokens(
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.bond_vault.to_account_info(),
+                ctx.accounts.bond_vault_auth.to_account_info(),
+                &[bond_seeds],
+                ctx.accounts.bonding_curve_ata.to_account_info(),
+                config.supply_finalized && holders_usdc > 0,
+                amount,
+            )?;
+            ctx.accounts.mint.reload()?;
+            ctx.accounts.bonding_curve_ata.reload()?;
+        }
+
+        let supply = circulating_supply(&ctx.accounts.mint, &ctx.accounts.bonding_curve_ata);
+        let config = &mut ctx.accounts.launch_config;
+        config.holders_reserve_usdc = config.holders_reserve_usdc.saturating_add(holders_usdc);
+        config.accrue_holders_index(holders_usdc, supply);
+
+        emit!(BondSlashedEvent {
+            launch: config.key(),
+            amount,
+            holders_usdc,
+            creator_tokens_bought: ctx.accounts.creator_ledger.tokens_bought,
+            creator_tokens_sold: ctx.accounts.creator_ledger.tokens_sold,
+        });
+        Ok(())
+    }
+
+    // Returned once the holding period after graduation has passed, or straight away when
+    // the launch simply failed. Either way the creator must still be within the sell limit.
+    pub fn release_bond(ctx: Context<ReleaseBond>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        require!(config.bond_posted && !config.bond_settled, LaunchError::BondSettled);
+        require!(!config.bond_violated(&ctx.accounts.creator_ledger), LaunchError::BondLocked);
+        let unlocked = if config.graduated_at != 0 {
+            now >= config.graduated_at.saturating_add(config.bond_holding_period)
+        } else {
+            config.phase == LaunchPhase::Failed || config.phase == LaunchPhase::Archived
+        };
+        require!(unlocked, LaunchError::BondLocked);
+        config.bond_settled = true;
+
+        let amount = ctx.accounts.bond_vault.amount;
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.bond_token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    to: ctx.accounts.creator_bond_ata.to_account_info(),
+                    authority: ctx.accounts.bond_vault_auth.to_account_info(),
+                },
+                &[&[b"bond-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.bond_vault_auth]]],
+            ),
+            amount,
+        )?;
+
+        emit!(BondReleasedEvent { launch: config.key(), amount });
+        Ok(())
+    }
+
+    pub fn set_merkle_root(ctx: Context<SetMerkleRoot>, merkle_root: [u8; 32]) -> Result<()> {
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&[LaunchPhase::Scheduled])?;
+        require!(Clock::get()?.unix_timestamp < config.start_time, LaunchError::AlreadyStarted);
+        require!(config.has_presale(), LaunchError::InvalidPresale);
+        require!(merkle_root != [0u8; 32], LaunchError::InvalidPresale);
+
+        config.merkle_root = merkle_root;
+        emit!(AllowlistUpdatedEvent { launch: config.key(), merkle_root });
+        Ok(())
+    }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        require!(ctx.accounts.launch_config.graduated_at == 0, LaunchError::MetadataFrozen);
+
+        let (current, updated) = {
+            let data = ctx.accounts.mint.to_account_info().try_borrow_data()?.to_vec();
+            let state = StateWithExtensions::<MintState>::unpack(&data)?;
+            let current = state.get_variable_len_extension::<TokenMetadata>()?;
+            let mut updated = current.clone();
+            if let Some(v) = &name {
+                updated.name = v.clone();
+            }
+            if let Some(v) = &symbol {
+                updated.symbol = v.clone();
+            }
+            if let Some(v) = &uri {
+                updated.uri = v.clone();
+            }
+            (current, updated)
+        };
+        validate_metadata(&updated.name, &updated.symbol, &updated.uri)?;
+        // the symbol may change case or spacing, not its registered ticker
+        require!(
+            normalize_ticker(&updated.symbol) == normalize_ticker(&current.symbol),
+            LaunchError::TickerImmutable
+        );
+        fund_metadata_growth(
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            updated.tlv_size_of()?.saturating_sub(current.tlv_size_of()?),
+        )?;
+
+        let mint_key = ctx.accounts.mint.key();
+        let mint_seeds: &[&[u8]] = &[b"mint-auth", mint_key.as_ref(), &[ctx.bumps.mint_auth]];
+        for (field, value) in [(Field::Name, name), (Field::Symbol, symbol), (Field::Uri, uri)] {
+            if let Some(value) = value {
+                token_interface::token_metadata_update_field(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_2022_program.to_account_info(),
+                        TokenMetadataUpdateField {
+                            program_id: ctx.accounts.token_2022_program.to_account_info(),
+                            metadata: ctx.accounts.mint.to_account_info(),
+                            update_authority: ctx.accounts.mint_auth.to_account_info(),
+                        },
+                        &[mint_seeds],
+                    ),
+                    field,
+                    value,
+                )?;
+            }
+        }
+
+        emit!(MetadataUpdatedEvent {
+            launch: ctx.accounts.launch_config.key(),
+            name: updated.name,
+            symbol: updated.symbol,
+            uri: updated.uri,
+        });
+        Ok(())
+    }
+
+    // Permissionless: frees a ReusableAfterFailure ticker once its launch failed without graduating.
+    pub fn release_ticker(ctx: Context<ReleaseTicker>) -> Result<()> {
+        let config = &ctx.accounts.launch_config;
+        let record = &mut ctx.accounts.ticker_record;
+        require!(
+            record.policy == TickerPolicy::ReusableAfterFailure
+                && config.graduated_at == 0
+                && matches!(config.phase, LaunchPhase::Failed | LaunchPhase::Archived),
+            LaunchError::TickerNotReleasable
+        );
+        let launch = record.launch;
+        record.launch = Pubkey::default();
+        record.mint = Pubkey::default();
+        record.claimed_at = 0;
+        emit!(TickerReleasedEvent { symbol: record.symbol.clone(), launch });
+        Ok(())
+    }
+
+    // Platform override for disputed or reserved symbols: `mint` None clears the owner, so
+    // with Reserved the ticker is held back from create_token.
+    pub fn assign_ticker(
+        ctx: Context<AssignTicker>,
+        symbol: String,
+        mint: Option<Pubkey>,
+        policy: TickerPolicy,
+    ) -> Result<()> {
+        let ticker = normalize_ticker(&symbol);
+        validate_ticker(&ticker)?;
+        let (launch, mint) = match mint {
+            Some(m) => (Pubkey::find_program_address(&[b"launch", m.as_ref()], &crate::ID).0, m),
+            None => (Pubkey::default(), Pubkey::default()),
+        };
+        let record = &mut ctx.accounts.ticker_record;
+        record.symbol = ticker;
+        record.launch = launch;
+        record.mint = mint;
+        record.claimed_at = if mint == Pubkey::default() { 0 } else { Clock::get()?.unix_timestamp };
+        record.policy = policy;
+        record.bump = ctx.bumps.ticker_record;
+        emit!(TickerAssignedEvent { symbol: record.symbol.clone(), launch, mint, policy });
+        Ok(())
+    }
+
+    pub fn quote_buy(ctx: Context<Quote>, usdc_amount: u64) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        Ok(ctx.accounts.launch_config.curve_buy(usdc_amount, now))
+    }
+
+    pub fn quote_sell(ctx: Context<Quote>, token_amount: u64) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        Ok(ctx.accounts.launch_config.curve_sell(token_amount, now))
+    }
+
+    pub fn register_buyer(ctx: Context<RegisterBuyer>) -> Result<()> {
+        let config = &ctx.accounts.launch_config;
+        require!(Clock::get()?.unix_timestamp <= config.deadline, LaunchError::Ended);
+
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        ledger.buyer = ctx.accounts.buyer.key();
+        ledger.bump = ctx.bumps.buyer_ledger;
+        Ok(())
+    }
+
+    pub fn buy(
+        ctx: Context<Buy>,
+        usdc_amount: u64,
+        min_tokens_out: u64,
+        deadline: i64,
+        allowlist: Option<AllowlistProof>,
+    ) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(usdc_amount > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= deadline, LaunchError::Deadline);
+
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        require!(now >= config.start_time, LaunchError::NotStarted);
+        config.require_phase(&[LaunchPhase::Presale, LaunchPhase::Curve])?;
+        require!(now <= config.deadline, LaunchError::Ended);
+        require!(
+            config.phase != LaunchPhase::Curve || !config.has_commit_reveal() || now >= config.reveal_end(),
+            LaunchError::CommitRevealActive
+        );
+        require!(
+            config.phase != LaunchPhase::Curve || !config.has_batch_trading(),
+            LaunchError::BatchTradingOnly
+        );
+        require!(!config.in_trade, LaunchError::Reentrancy); // reentrancy guard
+        config.in_trade = true;
+
+        if config.sandwich_guard {
+            enforce_sandwich_guard(
+                ctx.accounts.instructions.as_ref(),
+                ctx.accounts.slot_activity.as_mut(),
+                ctx.bumps.slot_activity,
+                launch,
+                ctx.accounts.buyer.key(),
+                true,
+            )?;
+        }
+
+        // presale: allowlisted buyers only, capped per leaf
+        if config.phase == LaunchPhase::Presale {
+            let entry = allowlist.as_ref().ok_or(LaunchError::ProofRequired)?;
+            require!(
+                verify_allowlist(&config.merkle_root, &ctx.accounts.buyer.key(), entry.allocation_usdc, &entry.proof),
+                LaunchError::InvalidProof
+            );
+            let ledger = &mut ctx.accounts.buyer_ledger;
+            let used = ledger
+                .presale_paid_usdc
+                .checked_add(usdc_amount)
+                .ok_or(LaunchError::Overflow)?;
+            require!(used <= entry.allocation_usdc, LaunchError::AllocationExceeded);
+            ledger.presale_paid_usdc = used;
+        }
+
+        // calculate tokens out based on bonding curve (time-weighted in LBP mode)
+        let tokens_out = config.curve_buy(usdc_amount, now);
+        require!(tokens_out >= min_tokens_out, LaunchError::Slippage);
+
+        // anti-snipe: first 20s of public trading max 0.1% of supply (commit-reveal replaces it)
+        let time_passed = now - config.curve_start_time;
+        if !config.has_commit_reveal() && (0..=20).contains(&time_passed) {
+            // snipe_max_pct in 1/10,000 of supply; enforce tokens_out <= snipe_max_pct/10000 * total_supply
+            let max_tokens = (config.total_supply as u128)
+                .saturating_mul(config.snipe_max_pct as u128)
+                .checked_div(10_000)
+                .unwrap_or(0) as u64;
+            require!(tokens_out <= max_tokens, LaunchError::SnipeSize);
+        }
+
+        // transfer USDC into the USDC vault
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_usdc.to_account_info(),
+                    to: ctx.accounts.usdc_vault.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            usdc_amount,
+        )?;
+
+        let (user_tokens, burn_amount) = issue_buy_tokens(
+            ctx.accounts.token_2022_program.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.buyer_x_ata.to_account_info(),
+            ctx.accounts.burn_ata.to_account_info(),
+            ctx.accounts.mint_auth.to_account_info(),
+            ctx.bumps.mint_auth,
+            ctx.accounts.bonding_curve_ata.to_account_info(),
+            launch_ai,
+            config.bump,
+            config.supply_finalized,
+            tokens_out,
+        )?;
+
+        config.apply_buy(usdc_amount, tokens_out);
+        config.update_breaker(launch, now, Clock::get()?.slot)?;
+
+        // ensure buyer ledger exists and update paid_usdc
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        if ledger.buyer == Pubkey::default() {
+            ledger.buyer = ctx.accounts.buyer.key();
+            ledger.bump = ctx.bumps.buyer_ledger;
+        }
+        ledger.paid_usdc = ledger.paid_usdc.saturating_add(usdc_amount);
+        ledger.tokens_bought = ledger.tokens_bought.saturating_add(user_tokens);
+
+        emit!(BuyEvent {
+            buyer: ctx.accounts.buyer.key(),
+            usdc_in: usdc_amount,
+            tokens_out: user_tokens,
+            burned: burn_amount,
+        });
+
+        config.in_trade = false;
+        Ok(())
+    }
+
+    pub fn commit_buy(ctx: Context<CommitBuy>, commitment: [u8; 32], escrow_usdc: u64) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(escrow_usdc > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&[LaunchPhase::Curve])?;
+        require!(config.has_commit_reveal(), LaunchError::InvalidCommitReveal);
+        require!(
+            now >= config.curve_start_time && now < config.commit_end(),
+            LaunchError::NotCommitWindow
+        );
+
+        let pending = &mut ctx.accounts.commitment;
+        pending.buyer = ctx.accounts.buyer.key();
+        pending.commitment = commitment;
+        pending.escrow_usdc = escrow_usdc;
+        pending.bump = ctx.bumps.commitment;
+
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_usdc.to_account_info(),
+                    to: ctx.accounts.commit_escrow.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            escrow_usdc,
+        )?;
+
+        emit!(BuyCommittedEvent { buyer: ctx.accounts.buyer.key(), escrow_usdc });
+        Ok(())
+    }
+
+    // Executes the committed buy against the curve at reveal time, so reveals fill in
+    // the order they land. A reveal that misses its min_tokens_out is refunded instead.
+    pub fn reveal_buy(
+        ctx: Context<RevealBuy>,
+        usdc_amount: u64,
+        min_tokens_out: u64,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        let now = Clock::get()?.unix_timestamp;
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&[LaunchPhase::Curve])?;
+        require!(
+            now >= config.commit_end() && now < config.reveal_end(),
+            LaunchError::NotRevealWindow
+        );
+        require!(!config.in_trade, LaunchError::Reentrancy);
+        config.in_trade = true;
+
+        let buyer = ctx.accounts.buyer.key();
+        let pending = &ctx.accounts.commitment;
+        let expected = keccak::hashv(&[
+            buyer.as_ref(),
+            &usdc_amount.to_le_bytes(),
+            &min_tokens_out.to_le_bytes(),
+            &salt,
+        ])
+        .0;
+        require!(expected == pending.commitment, LaunchError::CommitmentMismatch);
+        require!(usdc_amount <= pending.escrow_usdc, LaunchError::CommitmentMismatch);
+
+        let tokens_out = if usdc_amount > 0 { config.curve_buy(usdc_amount, now) } else { 0 };
+        let filled = usdc_amount > 0 && tokens_out >= min_tokens_out;
+        let spent = if filled { usdc_amount } else { 0 };
+        let refund = pending.escrow_usdc.saturating_sub(spent);
+        let mint_key = ctx.accounts.mint.key();
+        let escrow_seeds: &[&[u8]] = &[b"commit-escrow", mint_key.as_ref(), &[ctx.bumps.commit_escrow_auth]];
+
+        if filled {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.commit_escrow.to_account_info(),
+                        to: ctx.accounts.usdc_vault.to_account_info(),
+                        authority: ctx.accounts.commit_escrow_auth.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                spent,
+            )?;
+            let (user_tokens, burn_amount) = issue_buy_tokens(
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.buyer_x_ata.to_account_info(),
+                ctx.accounts.burn_ata.to_account_info(),
+                ctx.accounts.mint_auth.to_account_info(),
+                ctx.bumps.mint_auth,
+                ctx.accounts.bonding_curve_ata.to_account_info(),
+                launch_ai,
+                config.bump,
+                config.supply_finalized,
+                tokens_out,
+            )?;
+            config.apply_buy(spent, tokens_out);
+
+            let ledger = &mut ctx.accounts.buyer_ledger;
+            if ledger.buyer == Pubkey::default() {
+                ledger.buyer = buyer;
+                ledger.bump = ctx.bumps.buyer_ledger;
+            }
+            ledger.paid_usdc = ledger.paid_usdc.saturating_add(spent);
+            ledger.tokens_bought = ledger.tokens_bought.saturating_add(user_tokens);
+
+            emit!(BuyEvent {
+                buyer,
+                usdc_in: spent,
+                tokens_out: user_tokens,
+                burned: burn_amount,
+            });
+        }
+        if refund > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.commit_escrow.to_account_info(),
+                        to: ctx.accounts.buyer_usdc.to_account_info(),
+                        authority: ctx.accounts.commit_escrow_auth.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                refund,
+            )?;
+            emit!(CommitmentRefundedEvent { buyer, amount: refund });
+        }
+
+        let launch = config.key();
+        config.update_breaker(launch, now, Clock::get()?.slot)?;
+        config.in_trade = false;
+        Ok(())
+    }
+
+    pub fn reclaim_commitment(ctx: Context<ReclaimCommitment>) -> Result<()> {
+        let config = &ctx.accounts.launch_config;
+        require!(Clock::get()?.unix_timestamp >= config.reveal_end(), LaunchError::TooEarly);
+
+        let amount = ctx.accounts.commitment.escrow_usdc;
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.commit_escrow.to_account_info(),
+                    to: ctx.accounts.buyer_usdc.to_account_info(),
+                    authority: ctx.accounts.commit_escrow_auth.to_account_info(),
+                },
+                &[&[b"commit-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.commit_escrow_auth]]],
+            ),
+            amount,
+        )?;
+
+        emit!(CommitmentRefundedEvent { buyer: ctx.accounts.buyer.key(), amount });
+        Ok(())
+    }
+
+    pub fn place_order(ctx: Context<PlaceOrder>, is_buy: bool, amount_in: u64, min_out: u64) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(amount_in > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&[LaunchPhase::Curve])?;
+        require!(config.has_batch_trading(), LaunchError::InvalidBatchTrading);
+        require!(now <= config.deadline, LaunchError::Ended);
+        require!(!config.has_commit_reveal() || now >= config.reveal_end(), LaunchError::CommitRevealActive);
+
+        let (out_mint, in_escrow, in_program) = if is_buy {
+            (
+                ctx.accounts.mint.key(),
+                ctx.accounts.batch_usdc_escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            )
+        } else {
+            (
+                USDC_DEVNET,
+                ctx.accounts.batch_token_escrow.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+            )
+        };
+        require!(ctx.accounts.destination.mint == out_mint, LaunchError::BadDestination);
+
+        let book = &mut ctx.accounts.order_book;
+        if book.launch == Pubkey::default() {
+            book.launch = launch;
+            book.bump = ctx.bumps.order_book;
+        }
+        require!(book.orders.len() < MAX_BATCH_ORDERS, LaunchError::BookFull);
+        if book.orders.is_empty() {
+            book.batch_start_slot = slot;
+        }
+        // settle_batch records fills on this ledger and credits expired orders back to it
+        let ledger = &mut ctx.accounts.trader_ledger;
+        if ledger.buyer == Pubkey::default() {
+            ledger.buyer = ctx.accounts.trader.key();
+            ledger.bump = ctx.bumps.trader_ledger;
+        }
+        let batch_id = book.batch_id;
+        book.orders.push(BatchOrder {
+            owner: ctx.accounts.trader.key(),
+            destination: ctx.accounts.destination.key(),
+            is_buy,
+            amount_in,
+            min_out,
+            batch_id,
+        });
+
+        token_interface::transfer(
+            CpiContext::new(
+                in_program,
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: in_escrow,
+                    authority: ctx.accounts.trader.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+
+        emit!(OrderPlacedEvent {
+            owner: ctx.accounts.trader.key(),
+            batch_id: book.batch_id,
+            is_buy,
+            amount_in,
+            min_out,
+        });
+        Ok(())
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>, index: u8) -> Result<()> {
+        let book = &mut ctx.accounts.order_book;
+        let order = book.orders.get(index as usize).cloned().ok_or(LaunchError::OrderNotFound)?;
+        require!(order.owner == ctx.accounts.trader.key(), LaunchError::NotYourLedger);
+        book.orders.remove(index as usize);
+
+        let (refund_mint, escrow, program) = if order.is_buy {
+            (
+                USDC_DEVNET,
+                ctx.accounts.batch_usdc_escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            )
+        } else {
+            (
+                ctx.accounts.mint.key(),
+                ctx.accounts.batch_token_escrow.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+            )
+        };
+        require!(ctx.accounts.refund_account.mint == refund_mint, LaunchError::BadDestination);
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                program,
+                Transfer {
+                    from: escrow,
+                    to: ctx.accounts.refund_account.to_account_info(),
+                    authority: ctx.accounts.batch_escrow_auth.to_account_info(),
+                },
+                &[&[b"batch-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.batch_escrow_auth]]],
+            ),
+            order.amount_in,
+        )?;
+
+        emit!(OrderCancelledEvent { owner: order.owner, is_buy: order.is_buy, amount_in: order.amount_in });
+        Ok(())
+    }
+
+    // Settles every order in the book at one price. Against a constant-product curve the
+    // uniform price that nets buys (B USDC) against sells (S tokens) and routes only the
+    // imbalance through the curve is P = (x + B) / (y + S), which keeps x * y unchanged.
+    // Orders whose min_out fails at P, and sells held back by the circuit breaker, carry over
+    // for at most MAX_ORDER_BATCHES batches. Orders past that, orders whose destination can no
+    // longer receive the fill, and every order once the curve has closed are credited to the
+    // owner's ledger instead (claim_batch_refund).
+    // remaining_accounts: each order's destination and its owner's buyer ledger, in book order.
+    pub fn settle_batch<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let now = Clock::get()?.unix_timestamp;
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let config = &mut ctx.accounts.launch_config;
+        // once the curve has closed nothing fills; the book is only emptied
+        let open = config.phase == LaunchPhase::Curve && now <= config.deadline;
+        if open {
+            ctx.accounts.platform_config.require_unpaused(config, PAUSE_TRADES)?;
+        }
+        require!(!config.in_trade, LaunchError::Reentrancy);
+        config.in_trade = true;
+
+        let book = &mut ctx.accounts.order_book;
+        require!(!book.orders.is_empty(), LaunchError::ZeroAmount);
+        require!(
+            !open || slot >= book.batch_start_slot.saturating_add(config.batch_interval_slots),
+            LaunchError::BatchOpen
+        );
+        require!(ctx.remaining_accounts.len() == 2 * book.orders.len(), LaunchError::BadDestination);
+        let order_accounts: Vec<(&AccountInfo<'info>, &AccountInfo<'info>)> =
+            ctx.remaining_accounts.chunks(2).map(|c| (&c[0], &c[1])).collect();
+
+        let mint_key = ctx.accounts.mint.key();
+        let mut deliverable = Vec::with_capacity(book.orders.len());
+        for (o, (dest, _)) in book.orders.iter().zip(&order_accounts) {
+            require!(dest.key() == o.destination, LaunchError::BadDestination);
+            let (program, out_mint) = if o.is_buy {
+                (ctx.accounts.token_2022_program.key(), mint_key)
+            } else {
+                (ctx.accounts.token_program.key(), USDC_DEVNET)
+            };
+            deliverable.push(open && can_receive(dest, &program, &out_mint));
+        }
+
+        let x = config.virtual_usdc as u128;
+        let y = config.virtual_token as u128;
+        // sells wait in the book while the circuit breaker is active
+        let halted = config.breaker_halted(now);
+        let mut included: Vec<bool> = book
+            .orders
+            .iter()
+            .zip(&deliverable)
+            .map(|(o, ok)| *ok && (o.is_buy || !halted))
+            .collect();
+        let (num, den) = config.match_batch(&book.orders, &mut included, now, slot);
+        let buy_usdc = (num - x) as u64;
+        let sell_tokens = (den - y) as u64;
+
+        let escrow_seeds: &[&[u8]] = &[b"batch-escrow", mint_key.as_ref(), &[ctx.bumps.batch_escrow_auth]];
+        let vault_seeds: &[&[u8]] = &[b"usdc-vault", mint_key.as_ref(), &[ctx.bumps.usdc_vault_auth]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let token_2022_program = ctx.accounts.token_2022_program.to_account_info();
+        let curve_ai = ctx.accounts.bonding_curve_ata.to_account_info();
+
+        if buy_usdc > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    Transfer {
+                        from: ctx.accounts.batch_usdc_escrow.to_account_info(),
+                        to: ctx.accounts.usdc_vault.to_account_info(),
+                        authority: ctx.accounts.batch_escrow_auth.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                buy_usdc,
+            )?;
+        }
+        if sell_tokens > 0 {
+            return_tokens(
+                token_2022_program.clone(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.batch_token_escrow.to_account_info(),
+                ctx.accounts.batch_escrow_auth.to_account_info(),
+                &[escrow_seeds],
+                curve_ai.clone(),
+                config.supply_finalized,
+                sell_tokens,
+            )?;
+        }
+
+        let mut supply = circulating_supply(&ctx.accounts.mint, &ctx.accounts.bonding_curve_ata)
+            .saturating_sub(sell_tokens);
+        let mut holders_share = 0u64;
+        let mut carried = Vec::with_capacity(book.orders.len());
+        let mut refunded_orders = 0u8;
+        let batch_id = book.batch_id;
+        let orders = book.orders.iter().zip(&included).zip(&deliverable).zip(&order_accounts);
+        for (((o, inc), ok), (dest, ledger_ai)) in orders {
+            if !*inc {
+                if *ok && !o.expired(batch_id) {
+                    carried.push(o.clone());
+                } else {
+                    credit_batch_refund(ledger_ai, &mint_key, o)?;
+                    refunded_orders += 1;
+                    emit!(OrderRefundedEvent {
+                        owner: o.owner,
+                        batch_id: o.batch_id,
+                        is_buy: o.is_buy,
+                        amount_in: o.amount_in,
+                    });
+                }
+                continue;
+            }
+            if o.is_buy {
+                let tokens_out = ((o.amount_in as u128) * den / num) as u64;
+                // the burn share is simply never minted (or burned from the inventory)
+                let burned = tokens_out.saturating_mul(BURN_BUY_PCT as u64) / 100;
+                let user_tokens = tokens_out.saturating_sub(burned);
+                issue_tokens(
+                    token_2022_program.clone(),
+                    ctx.accounts.mint.to_account_info(),
+                    (*dest).clone(),
+                    ctx.accounts.mint_auth.to_account_info(),
+                    ctx.bumps.mint_auth,
+                    curve_ai.clone(),
+                    launch_ai.clone(),
+                    config.bump,
+                    config.supply_finalized,
+                    user_tokens,
+                )?;
+                if config.supply_finalized && burned > 0 {
+                    burn_inventory(
+                        token_2022_program.clone(),
+                        ctx.accounts.mint.to_account_info(),
+                        curve_ai.clone(),
+                        launch_ai.clone(),
+                        config.bump,
+                        burned,
+                    )?;
+                }
+                supply = supply.saturating_add(user_tokens);
+                record_batch_fill(ledger_ai, &mint_key, o, user_tokens)?;
+                emit!(BuyEvent { buyer: o.owner, usdc_in: o.amount_in, tokens_out: user_tokens, burned });
+            } else {
+                let usdc_out = ((o.amount_in as u128) * num / den) as u64;
+                let tax = sell_tax(o.amount_in, usdc_out, config.total_supply);
+                holders_share = holders_share.saturating_add(config.accrue_sell_tax(tax));
+                let user_usdc = usdc_out.saturating_sub(tax);
+                token_interface::transfer(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        Transfer {
+                            from: ctx.accounts.usdc_vault.to_account_info(),
+                            to: (*dest).clone(),
+                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                        },
+                        &[vault_seeds],
+                    ),
+                    user_usdc,
+                )?;
+                record_batch_fill(ledger_ai, &mint_key, o, o.amount_in)?;
+                emit!(SellEvent { seller: o.owner, tokens_in: o.amount_in, usdc_out: user_usdc, tax });
+            }
+        }
+
+        // only the imbalance moved the curve; x * y is preserved
+        config.virtual_usdc = (num * y / den) as u64;
+        config.virtual_token = (x * den / num) as u64;
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+        config.total_raised = config.total_raised.saturating_add(buy_usdc);
+        config.accrue_holders_index(holders_share, supply);
+        if open {
+            let launch = config.key();
+            config.update_breaker(launch, now, slot)?;
+        }
+
+        let filled_orders = (book.orders.len() - carried.len()) as u8 - refunded_orders;
+        book.orders = carried;
+        book.batch_id = book.batch_id.saturating_add(1);
+        book.batch_start_slot = slot;
+
+        emit!(BatchSettledEvent {
+            launch: config.key(),
+            batch_id,
+            clearing_price: (num * 10u128.pow(TOKEN_DECIMALS as u32) / den) as u64,
+            buy_usdc,
+            sell_tokens,
+            filled_orders,
+            carried_orders: book.orders.len() as u8,
+            refunded_orders,
+        });
+
+        config.in_trade = false;
+        Ok(())
+    }
+
+    // Pays out what settle_batch credited to the ledger. Never paused: it is a refund path.
+    pub fn claim_batch_refund(ctx: Context<ClaimBatchRefund>) -> Result<()> {
+        let ledger = &mut ctx.accounts.trader_ledger;
+        let (usdc, tokens) = (ledger.batch_refund_usdc, ledger.batch_refund_tokens);
+        require!(usdc > 0 || tokens > 0, LaunchError::ZeroEntitled);
+        ledger.batch_refund_usdc = 0;
+        ledger.batch_refund_tokens = 0;
+
+        let mint_key = ctx.accounts.mint.key();
+        let escrow_seeds: &[&[u8]] = &[b"batch-escrow", mint_key.as_ref(), &[ctx.bumps.batch_escrow_auth]];
+        if usdc > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.batch_usdc_escrow.to_account_info(),
+                        to: ctx.accounts.usdc_refund_account.to_account_info(),
+                        authority: ctx.accounts.batch_escrow_auth.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                usdc,
+            )?;
+        }
+        if tokens > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_2022_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.batch_token_escrow.to_account_info(),
+                        to: ctx.accounts.token_refund_account.to_account_info(),
+                        authority: ctx.accounts.batch_escrow_auth.to_account_info(),
+                    },
+                    &[escrow_seeds],
+                ),
+                tokens,
+            )?;
+        }
+
+        emit!(BatchRefundClaimedEvent { owner: ctx.accounts.trader.key(), usdc, tokens });
+        Ok(())
+    }
+
+    pub fn buy_fixed_price(ctx: Context<BuyFixedPrice>, token_amount: u64) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(token_amount > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        require!(now >= config.start_time, LaunchError::NotStarted);
+        config.require_phase(&[LaunchPhase::FixedPriceRound])?;
+
+        let sold = config
+            .round_tokens_sold
+            .checked_add(token_amount)
+            .ok_or(LaunchError::Overflow)?;
+        require!(sold <= config.round_max_tokens, LaunchError::RoundSoldOut);
+
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        let wallet_tokens = ledger
+            .round_tokens
+            .checked_add(token_amount)
+            .ok_or(LaunchError::Overflow)?;
+        require!(wallet_tokens <= config.round_wallet_cap, LaunchError::RoundWalletCap);
+
+        // round up so the buyer never pays less than the round price
+        let unit = 10u128.pow(TOKEN_DECIMALS as u32);
+        let usdc_in = (token_amount as u128)
+            .checked_mul(config.round_price as u128)
+            .and_then(|v| v.checked_add(unit - 1))
+            .map(|v| v / unit)
+            .ok_or(LaunchError::Overflow)?;
+        let usdc_in = u64::try_from(usdc_in).map_err(|_| LaunchError::Overflow)?;
+        require!(usdc_in > 0, LaunchError::ZeroAmount);
+
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.buyer_usdc.to_account_info(),
+                    to: ctx.accounts.usdc_vault.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                },
+            ),
+            usdc_in,
+        )?;
+
+        if ledger.buyer == Pubkey::default() {
+            ledger.buyer = ctx.accounts.buyer.key();
+            ledger.bump = ctx.bumps.buyer_ledger;
+        }
+        ledger.round_tokens = wallet_tokens;
+        ledger.round_paid_usdc = ledger.round_paid_usdc.saturating_add(usdc_in);
+        ledger.paid_usdc = ledger.paid_usdc.saturating_add(usdc_in);
+        config.round_tokens_sold = sold;
+        config.round_raised = config.round_raised.saturating_add(usdc_in);
+
+        emit!(FixedPriceBuyEvent {
+            buyer: ctx.accounts.buyer.key(),
+            usdc_in,
+            tokens: token_amount,
+        });
+
+        // a sell-out closes the round immediately
+        config.sync_phase(launch, now)?;
+        Ok(())
+    }
+
+    pub fn claim_fixed_price_tokens(ctx: Context<ClaimFixedPriceTokens>) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)?;
+        let now = Clock::get()?.unix_timestamp;
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&POST_START_PHASES)?;
+        require!(config.round_succeeded(), LaunchError::SoftCapMissed);
+
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        let amount = ledger.round_tokens;
+        require!(amount > 0, LaunchError::ZeroEntitled);
+        ledger.round_tokens = 0;
+        ledger.tokens_bought = ledger.tokens_bought.saturating_add(amount);
+        config.presale_claimed = config.presale_claimed.saturating_add(amount);
+
+        issue_tokens(
+            ctx.accounts.token_2022_program.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.buyer_x_ata.to_account_info(),
+            ctx.accounts.mint_auth.to_account_info(),
+            ctx.bumps.mint_auth,
+            ctx.accounts.bonding_curve_ata.to_account_info(),
+            launch_ai,
+            config.bump,
+            config.supply_finalized,
+            amount,
+        )?;
+
+        emit!(ClaimEvent { user: ctx.accounts.buyer.key(), amount });
+        Ok(())
+    }
+
+    pub fn refund_fixed_price(ctx: Context<RefundFixedPrice>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&[LaunchPhase::Failed, LaunchPhase::Archived])?;
+        require!(!config.round_succeeded(), LaunchError::SoftCapMet);
+
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        let amount = ledger.round_paid_usdc;
+        require!(amount > 0, LaunchError::NeverPaid);
+        ledger.round_paid_usdc = 0;
+        ledger.round_tokens = 0;
+        config.round_raised = config.round_raised.saturating_sub(amount);
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.buyer_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            amount,
+        )?;
+
+        emit!(FixedPriceRefundEvent { buyer: ctx.accounts.buyer.key(), amount });
+        Ok(())
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, quote_amount: u64, tick: u8) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(quote_amount > 0, LaunchError::ZeroAmount);
+        require!((tick as usize) < AUCTION_TICKS, LaunchError::InvalidTick);
+        let now = Clock::get()?.unix_timestamp;
+
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        require!(now >= config.start_time, LaunchError::NotStarted);
+        config.require_phase(&[LaunchPhase::BatchAuction])?;
+        require!(now < config.curve_start_time, LaunchError::AuctionClosed);
+
+        let bid = &mut ctx.accounts.bid;
+        if bid.quote_amount == 0 {
+            bid.bidder = ctx.accounts.bidder.key();
+            bid.tick = tick;
+            bid.bump = ctx.bumps.bid;
+        }
+        require!(bid.tick == tick, LaunchError::TickMismatch);
+        bid.quote_amount = bid.quote_amount.checked_add(quote_amount).ok_or(LaunchError::Overflow)?;
+
+        let book = &mut ctx.accounts.auction_book;
+        if book.launch == Pubkey::default() {
+            book.launch = launch;
+            book.bump = ctx.bumps.auction_book;
+        }
+        let slot = &mut book.demand[tick as usize];
+        *slot = slot.checked_add(quote_amount).ok_or(LaunchError::Overflow)?;
+
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bidder_usdc.to_account_info(),
+                    to: ctx.accounts.auction_escrow.to_account_info(),
+                    authority: ctx.accounts.bidder.to_account_info(),
+                },
+            ),
+            quote_amount,
+        )?;
+
+        emit!(AuctionBidEvent {
+            bidder: ctx.accounts.bidder.key(),
+            quote_amount,
+            max_price: config.auction_tick_price(tick),
+        });
+        Ok(())
+    }
+
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        config.require_phase(&[LaunchPhase::BatchAuction])?;
+        require!(now >= config.curve_start_time, LaunchError::AuctionOpen);
+
+        let demand = ctx.accounts.auction_book.as_ref().map(|b| b.demand).unwrap_or([0u64; AUCTION_TICKS]);
+        let AuctionClearing { marginal_tick, marginal_demand, marginal_fill, filled, tokens_filled, clearing_price } =
+            config.clear_auction(&demand);
+
+        config.auction_marginal_tick = marginal_tick;
+        config.auction_marginal_demand = marginal_demand;
+        config.auction_marginal_fill = marginal_fill;
+        config.auction_quote_filled = filled;
+        config.auction_tokens_filled = tokens_filled;
+        config.auction_clearing_price = clearing_price;
+
+        if filled > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.auction_escrow.to_account_info(),
+                        to: ctx.accounts.usdc_vault.to_account_info(),
+                        authority: ctx.accounts.auction_escrow_auth.to_account_info(),
+                    },
+                    &[&[b"auction-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.auction_escrow_auth]]],
+                ),
+                filled,
+            )?;
+        }
+
+        // continue on the ordinary curve from the cleared state
+        config.virtual_usdc = config.virtual_usdc.saturating_add(filled);
+        config.virtual_token = config.virtual_token.saturating_sub(tokens_filled);
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+        config.total_raised = config.total_raised.saturating_add(filled);
+        config.curve_start_time = now;
+        config.transition(launch, LaunchPhase::Curve)?;
+
+        emit!(AuctionSettledEvent {
+            launch,
+            clearing_price,
+            quote_filled: filled,
+            tokens_filled,
+        });
+        Ok(())
+    }
+
+    // While claims are paused only the unfilled quote is returned; the bid stays open
+    // until its tokens can be claimed.
+    pub fn claim_auction(ctx: Context<ClaimAuction>) -> Result<()> {
+        let paused = ctx
+            .accounts
+            .platform_config
+            .require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)
+            .is_err();
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&POST_START_PHASES)?;
+
+        let bid = &mut ctx.accounts.bid;
+        let fill = config.auction_fill(bid.tick, bid.quote_amount);
+        let tokens = if paused {
+            0
+        } else {
+            (fill as u128)
+                .saturating_mul(config.auction_tokens_filled as u128)
+                .checked_div(config.auction_quote_filled as u128)
+                .unwrap_or(0) as u64
+        };
+        let refund = if bid.refunded { 0 } else { bid.quote_amount.saturating_sub(fill) };
+        require!(!paused || refund > 0, LaunchError::Paused);
+        bid.refunded = true;
+        config.presale_claimed = config.presale_claimed.saturating_add(tokens);
+
+        if tokens > 0 {
+            issue_tokens(
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.bidder_x_ata.to_account_info(),
+                ctx.accounts.mint_auth.to_account_info(),
+                ctx.bumps.mint_auth,
+                ctx.accounts.bonding_curve_ata.to_account_info(),
+                launch_ai,
+                config.bump,
+                config.supply_finalized,
+                tokens,
+            )?;
+        }
+        if refund > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.auction_escrow.to_account_info(),
+                        to: ctx.accounts.bidder_usdc_ata.to_account_info(),
+                        authority: ctx.accounts.auction_escrow_auth.to_account_info(),
+                    },
+                    &[&[b"auction-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.auction_escrow_auth]]],
+                ),
+                refund,
+            )?;
+        }
+
+        if !paused {
+            ctx.accounts.bid.close(ctx.accounts.bidder.to_account_info())?;
+        }
+        emit!(AuctionClaimEvent { bidder: ctx.accounts.bidder.key(), tokens, refund });
+        Ok(())
+    }
+
+    pub fn enter_lottery(ctx: Context<EnterLottery>) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        require!(now >= config.start_time, LaunchError::NotStarted);
+        config.require_phase(&[LaunchPhase::Lottery])?;
+        require!(now < config.curve_start_time, LaunchError::Ended);
+
+        let ticket = &mut ctx.accounts.ticket;
+        ticket.entrant = ctx.accounts.entrant.key();
+        ticket.index = config.lottery_entries;
+        ticket.bump = ctx.bumps.ticket;
+        config.lottery_entries = config.lottery_entries.checked_add(1).ok_or(LaunchError::Overflow)?;
+
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.entrant_usdc.to_account_info(),
+                    to: ctx.accounts.lottery_escrow.to_account_info(),
+                    authority: ctx.accounts.entrant.to_account_info(),
+                },
+            ),
+            config.lottery_ticket_usdc,
+        )?;
+        Ok(())
+    }
+
+    pub fn commit_lottery_draw(ctx: Context<CommitLotteryDraw>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&[LaunchPhase::Lottery])?;
+        require!(now >= config.curve_start_time, LaunchError::RegistrationOpen);
+        // a commitment may only be replaced once its slot hash has aged out of SlotHashes
+        require!(
+            config.lottery_draw_slot == 0
+                || slot > config.lottery_draw_slot.saturating_add(SLOT_HASHES_MAX_ENTRIES),
+            LaunchError::DrawCommitted
+        );
+
+        config.lottery_draw_slot = slot + LOTTERY_DRAW_DELAY_SLOTS;
+        emit!(LotteryCommittedEvent {
+            launch: config.key(),
+            draw_slot: config.lottery_draw_slot,
+            entries: config.lottery_entries,
+        });
+        Ok(())
+    }
+
+    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.require_phase(&[LaunchPhase::Lottery])?;
+        require!(config.lottery_draw_slot != 0, LaunchError::DrawNotCommitted);
+        require!(clock.slot > config.lottery_draw_slot, LaunchError::DrawSlotPending);
+
+        let seed = {
+            let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+            slot_hash_at(&data, config.lottery_draw_slot).ok_or(LaunchError::DrawSlotExpired)?
+        };
+        let entries = config.lottery_entries as u64;
+        if entries > 0 {
+            let word = |at: usize| {
+                let mut w = [0u8; 8];
+                w.copy_from_slice(&seed[at..at + 8]);
+                u64::from_le_bytes(w)
+            };
+            let mut a = word(0) % entries;
+            while a == 0 || gcd(a, entries) != 1 {
+                a = (a + 1) % entries.max(2);
+            }
+            config.lottery_perm_a = a;
+            config.lottery_perm_b = word(8) % entries;
+        }
+        config.lottery_seed = seed;
+
+        let winners = config.lottery_entries.min(config.lottery_max_winners);
+        let raised = (winners as u64)
+            .checked_mul(config.lottery_ticket_usdc)
+            .ok_or(LaunchError::Overflow)?;
+        // tokens per ticket at the launch (spot) price
+        config.lottery_tokens_per_ticket = (config.lottery_ticket_usdc as u128)
+            .saturating_mul(config.virtual_token as u128)
+            .checked_div(config.virtual_usdc as u128)
+            .unwrap_or(0) as u64;
+
+        if raised > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.lottery_escrow.to_account_info(),
+                        to: ctx.accounts.usdc_vault.to_account_info(),
+                        authority: ctx.accounts.lottery_escrow_auth.to_account_info(),
+                    },
+                    &[&[b"lottery-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.lottery_escrow_auth]]],
+                ),
+                raised,
+            )?;
+        }
+
+        // curve continues from the launch price with the winners' USDC added
+        let new_usdc = config.virtual_usdc.saturating_add(raised);
+        config.virtual_token = (new_usdc as u128)
+            .saturating_mul(config.virtual_token as u128)
+            .checked_div(config.virtual_usdc as u128)
+            .unwrap_or(0)
+            .min(config.total_supply as u128) as u64;
+        config.virtual_usdc = new_usdc;
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+        config.total_raised = config.total_raised.saturating_add(raised);
+        config.curve_start_time = clock.unix_timestamp;
+        config.transition(launch, LaunchPhase::Curve)?;
+
+        emit!(LotteryDrawnEvent {
+            launch,
+            seed,
+            entries: config.lottery_entries,
+            winners,
+            tokens_per_ticket: config.lottery_tokens_per_ticket,
+        });
+        Ok(())
+    }
+
+    pub fn claim_lottery(ctx: Context<ClaimLottery>) -> Result<()> {
+        let launch_ai = ctx.accounts.launch_config.to_account_info();
+        let won = ctx.accounts.launch_config.lottery_is_winner(ctx.accounts.ticket.index);
+        // losing tickets are refunds and stay claimable while paused
+        if won {
+            ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)?;
+        }
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&POST_START_PHASES)?;
+        require!(config.has_lottery(), LaunchError::InvalidLottery);
+
+        let (tokens, refund) = if won {
+            (config.lottery_tokens_per_ticket, 0)
+        } else {
+            (0, config.lottery_ticket_usdc)
+        };
+        config.presale_claimed = config.presale_claimed.saturating_add(tokens);
+
+        if tokens > 0 {
+            issue_tokens(
+                ctx.accounts.token_2022_program.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.entrant_x_ata.to_account_info(),
+                ctx.accounts.mint_auth.to_account_info(),
+                ctx.bumps.mint_auth,
+                ctx.accounts.bonding_curve_ata.to_account_info(),
+                launch_ai,
+                config.bump,
+                config.supply_finalized,
+                tokens,
+            )?;
+        }
+        if refund > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.lottery_escrow.to_account_info(),
+                        to: ctx.accounts.entrant_usdc_ata.to_account_info(),
+                        authority: ctx.accounts.lottery_escrow_auth.to_account_info(),
+                    },
+                    &[&[b"lottery-escrow", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.lottery_escrow_auth]]],
+                ),
+                refund,
+            )?;
+        }
+
+        emit!(LotteryClaimEvent { entrant: ctx.accounts.entrant.key(), won, tokens, refund });
+        Ok(())
+    }
+
+    pub fn sell(
+        ctx: Context<Sell>,
+        token_amount: u64,
+        min_usdc_out: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_TRADES)?;
+        require!(token_amount > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now <= deadline, LaunchError::Deadline);
+
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.sync_phase(launch, now)?;
+        require!(now >= config.start_time, LaunchError::NotStarted);
+        config.require_phase(&[LaunchPhase::Curve])?;
+        require!(now <= config.deadline, LaunchError::Ended);
+        require!(!config.has_commit_reveal() || now >= config.reveal_end(), LaunchError::CommitRevealActive);
+        require!(!config.has_batch_trading(), LaunchError::BatchTradingOnly);
+        require!(!config.breaker_halted(now), LaunchError::BreakerHalted);
+        require!(!config.in_trade, LaunchError::Reentrancy);
+        config.in_trade = true;
+
+        if config.sandwich_guard {
+            enforce_sandwich_guard(
+                ctx.accounts.instructions.as_ref(),
+                ctx.accounts.slot_activity.as_mut(),
+                ctx.bumps.slot_activity,
+                launch,
+                ctx.accounts.seller.key(),
+                false,
+            )?;
+        }
+
+        let usdc_out = config.curve_sell(token_amount, now);
+        require!(usdc_out >= min_usdc_out, LaunchError::Slippage);
+        let price_after = config.price_at(
+            config.virtual_usdc.saturating_sub(usdc_out),
+            config.virtual_token.saturating_add(token_amount),
+            now,
+        );
+        config.check_breaker(price_after, now, Clock::get()?.slot)?;
+
+        // burn seller tokens, or return them to the inventory
+        let circulating = circulating_supply(&ctx.accounts.mint, &ctx.accounts.bonding_curve_ata);
+        return_tokens(
+            ctx.accounts.token_2022_program.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.seller_x_ata.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            &[],
+            ctx.accounts.bonding_curve_ata.to_account_info(),
+            config.supply_finalized,
+            token_amount,
+        )?;
+
+        let tax = sell_tax(token_amount, usdc_out, config.total_supply);
+        let holders_share = config.accrue_sell_tax(tax);
+
+        let ledger = &mut ctx.accounts.seller_ledger;
+        if ledger.buyer == Pubkey::default() {
+            ledger.buyer = ctx.accounts.seller.key();
+            ledger.bump = ctx.bumps.seller_ledger;
+        }
+        ledger.tokens_sold = ledger.tokens_sold.saturating_add(token_amount);
+
+        // Auto transfer platform share if threshold reached and liquidity allows
+        let available_for_platform = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
+        if available_for_platform >= config.auto_withdraw_threshold {
+            let reserved = config.holders_reserve_usdc.saturating_add(config.creator_reserve_usdc);
+            let vault_bal = ctx.accounts.usdc_vault.amount;
+            let free_liquidity = vault_bal.saturating_sub(reserved);
+            let amount = core::cmp::min(available_for_platform, free_liquidity);
+            if amount > 0 {
+                token_interface::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.usdc_vault.to_account_info(),
+                            to: ctx.accounts.platform_profit_ata.to_account_info(),
+                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                        },
+                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+                    ),
+                    amount,
+                )?;
+                config.platform_auto_transferred = config.platform_auto_transferred.saturating_add(amount);
+                config.virtual_usdc = config.virtual_usdc.saturating_sub(amount);
+                config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+            }
+        }
+
+        // compute user payout after tax
+        let user_usdc = usdc_out.saturating_sub(tax);
+
+        // pay user from vault
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.seller_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            user_usdc,
+        )?;
+
+        // update curve state
+        config.virtual_usdc = config.virtual_usdc.saturating_sub(usdc_out);
+        config.virtual_token = config.virtual_token.saturating_add(token_amount);
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+
+        let new_supply = circulating.saturating_sub(token_amount);
+        config.accrue_holders_index(holders_share, new_supply);
+        config.update_breaker(launch, now, Clock::get()?.slot)?;
+
+        emit!(SellEvent {
+            seller: ctx.accounts.seller.key(),
+            tokens_in: token_amount,
+            usdc_out: user_usdc,
+            tax,
+        });
+
+        config.in_trade = false;
+        Ok(())
+    }
+
+    pub fn claim_profits(ctx: Context<ClaimProfits>) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)?;
+        let user_balance = ctx.accounts.user_x_ata.amount; // current token holdings
+        require!(user_balance > 0, LaunchError::ZeroHolding);
+
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&POST_START_PHASES)?;
+        let ledger = &mut ctx.accounts.buyer_ledger;
+
+        // compute claimable using accumulator (bounded by reserves and free liquidity)
+        let delta_index = config.holders_index.saturating_sub(ledger.last_index_claimed);
+        let theoretical = (user_balance as u128)
+            .saturating_mul(delta_index)
+            .checked_div(ACC_SCALE)
+            .unwrap_or(0) as u64;
+        let reserved_holders = config.holders_reserve_usdc;
+        let reserved_platform = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
+        let reserved_creator = config.creator_reserve_usdc;
+        let vault_bal = ctx.accounts.usdc_vault.amount;
+        let free_liquidity = vault_bal
+            .saturating_sub(reserved_platform)
+            .saturating_sub(reserved_creator);
+        let claim_amount = core::cmp::min(theoretical, core::cmp::min(reserved_holders, free_liquidity));
+        require!(claim_amount > 0, LaunchError::ZeroEntitled);
+
+        // update ledger and reserves
+        ledger.last_claim = Clock::get()?.unix_timestamp;
+        ledger.last_index_claimed = config.holders_index;
+        config.holders_reserve_usdc = config.holders_reserve_usdc.saturating_sub(claim_amount);
+
+        // pay user
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.user_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            claim_amount,
+        )?;
+        config.virtual_usdc = config.virtual_usdc.saturating_sub(claim_amount);
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+
+        emit!(ClaimEvent { user: ctx.accounts.user.key(), amount: claim_amount });
+        Ok(())
+    }
+
+    // Token-side counterpart of claim_profits: pays the holder's share of harvested LP token fees.
+    pub fn claim_lp_token_fees(ctx: Context<ClaimLpTokenFees>) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_CLAIMS)?;
+        let user_balance = ctx.accounts.user_x_ata.amount;
+        require!(user_balance > 0, LaunchError::ZeroHolding);
+
+        let config = &mut ctx.accounts.launch_config;
+        let ledger = &mut ctx.accounts.buyer_ledger;
+        if ledger.buyer == Pubkey::default() {
+            ledger.buyer = ctx.accounts.user.key();
+            ledger.bump = ctx.bumps.buyer_ledger;
+        }
+        let delta_index = config.holders_token_index.saturating_sub(ledger.last_token_index_claimed);
+        let theoretical = (user_balance as u128)
+            .saturating_mul(delta_index)
+            .checked_div(ACC_SCALE)
+            .unwrap_or(0) as u64;
+        let claim_amount = theoretical
+            .min(config.holders_reserve_tokens)
+            .min(ctx.accounts.holders_token_vault.amount);
+        require!(claim_amount > 0, LaunchError::ZeroEntitled);
+
+        ledger.last_token_index_claimed = config.holders_token_index;
+        config.holders_reserve_tokens = config.holders_reserve_tokens.saturating_sub(claim_amount);
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.holders_token_vault.to_account_info(),
+                    to: ctx.accounts.user_x_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            claim_amount,
+        )?;
+
+        emit!(LpTokenFeesClaimedEvent { user: ctx.accounts.user.key(), amount: claim_amount });
+        Ok(())
+    }
+
+    pub fn reclaim_virtual_funds(ctx: Context<ReclaimVirtualFunds>) -> Result<()> {
+        let caller = ctx.accounts.creator.key();
+        let config = &mut ctx.accounts.launch_config;
+
+        let caller_type = classify_caller(&caller, config, None)?;
+        require!(caller_type == CallerType::Creator, LaunchError::NotYourLedger);
+        config.require_phase(&[LaunchPhase::Failed, LaunchPhase::Graduated])?;
+
+        let creator_refund = config.creator_paid_usdc;
+        if creator_refund > 0 {
+            let available = ctx.accounts.usdc_vault.amount;
+            let refund = core::cmp::min(creator_refund, available);
+            if refund > 0 {
+                token_interface::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.usdc_vault.to_account_info(),
+                            to: ctx.accounts.creator_usdc_ata.to_account_info(),
+                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                        },
+                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+                    ),
+                    refund,
+                )?;
+                config.creator_paid_usdc = config.creator_paid_usdc.saturating_sub(refund);
+            }
+        }
+
+        // nothing left owed to the creator: the launch is fully wound down
+        if config.creator_paid_usdc == 0 {
+            let launch = config.key();
+            config.transition(launch, LaunchPhase::Archived)?;
+        }
+        Ok(())
+    }
+
+    pub fn close_and_migrate_to_raydium(
+        ctx: Context<CloseAndMigrateToRaydium>,
+        burn_lp: bool,
+        unlock_schedule: Vec<LpTrancheParams>,
+    ) -> Result<()> {
+        let creator_refund = ctx.accounts.launch_config.creator_paid_usdc;
+
+        // Read immutable state and prepare seeds before taking a mutable borrow
+        ctx.accounts.launch_config.require_phase(&[LaunchPhase::Succeeded])?;
+
+        // unclaimed presale allocations stay behind in the curve inventory; the rest of it seeds the pool
+        let claims_reserved = ctx.accounts.launch_config.presale_owed();
+        let mint_key = ctx.accounts.mint.key();
+        if !ctx.accounts.launch_config.supply_finalized {
+            // mint what is left of the supply, then nobody can mint again; owed claims are paid
+            // from the curve inventory, so it must cover them without minting past total_supply
+            let mint_seeds: &[&[u8]] = &[b"mint-auth", mint_key.as_ref(), &[ctx.bumps.mint_auth]];
+            let minted = ctx
+                .accounts
+                .launch_config
+                .total_supply
+                .saturating_sub(ctx.accounts.mint.supply);
+            let inventory_before = ctx.accounts.bonding_curve_ata.amount;
+            require!(
+                inventory_before.saturating_add(minted) >= claims_reserved,
+                LaunchError::ClaimsUncovered
+            );
+            // claims draw on the existing inventory first; only the rest eats into the new mint
+            let claims_from_mint = claims_reserved.saturating_sub(inventory_before);
+            if minted > 0 {
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_2022_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: ctx.accounts.bonding_curve_ata.to_account_info(),
+                            authority: ctx.accounts.mint_auth.to_account_info(),
+                        },
+                        &[mint_seeds],
+                    ),
+                    minted,
+                )?;
+            }
+            token_interface::set_authority(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_2022_program.to_account_info(),
+                    SetAuthority {
+                        current_authority: ctx.accounts.mint_auth.to_account_info(),
+                        account_or_mint: ctx.accounts.mint.to_account_info(),
+                    },
+                    &[mint_seeds],
+                ),
+                AuthorityType::MintTokens,
+                None,
+            )?;
+            ctx.accounts.mint.reload()?;
+            ctx.accounts.bonding_curve_ata.reload()?;
+            ctx.accounts.launch_config.supply_finalized = true;
+            emit!(SupplyFinalized {
+                launch: ctx.accounts.launch_config.key(),
+                mint: mint_key,
+                supply: ctx.accounts.mint.supply,
+                minted,
+                liquidity_minted: minted.saturating_sub(claims_from_mint),
+                claims_reserved,
+            });
+        }
+
+        // metadata is frozen along with the supply
+        token_interface::token_metadata_update_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                TokenMetadataUpdateAuthority {
+                    program_id: ctx.accounts.token_2022_program.to_account_info(),
+                    metadata: ctx.accounts.mint.to_account_info(),
+                    current_authority: ctx.accounts.mint_auth.to_account_info(),
+                    new_authority: ctx.accounts.mint_auth.to_account_info(),
+                },
+                &[&[b"mint-auth", mint_key.as_ref(), &[ctx.bumps.mint_auth]]],
+            ),
+            OptionalNonZeroPubkey::default(),
+        )?;
+
+        // split balances (50%)
+        let token_half = ctx.accounts.bonding_curve_ata.amount.saturating_sub(claims_reserved) / 2;
+        let usdc_half = ctx.accounts.usdc_vault.amount / 2;
+
+        // Seeds and authority for launch_config PDA
+        let lc_bump = ctx.accounts.launch_config.bump;
+        let lc_ai = ctx.accounts.launch_config.to_account_info();
+        let lc_bump_arr = [lc_bump];
+        let lc_seeds: [&[u8]; 3] = [b"launch", mint_key.as_ref(), &lc_bump_arr];
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bonding_curve_ata.to_account_info(),
+                    to: ctx.accounts.coin_vault.to_account_info(),
+                    authority: lc_ai,
+                },
+                &[&lc_seeds],
+            ),
+            token_half,
+        )?;
+
+        // move USDC from vault
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.pc_vault.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            usdc_half,
+        )?;
+
+        // refund creator virtual funds from vault if available
+        if creator_refund > 0 {
+            let available = ctx.accounts.usdc_vault.amount;
+            let refund = core::cmp::min(creator_refund, available);
+            if refund > 0 {
+                token_interface::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.usdc_vault.to_account_info(),
+                            to: ctx.accounts.creator_usdc_ata.to_account_info(),
+                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                        },
+                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+                    ),
+                    refund,
+                )?;
+                let cfg = &mut ctx.accounts.launch_config;
+                cfg.creator_paid_usdc = cfg.creator_paid_usdc.saturating_sub(refund);
+            }
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let lp_amount = ctx.accounts.lp_lock_vault.amount;
+        require!(!burn_lp || unlock_schedule.is_empty(), LaunchError::InvalidUnlockSchedule);
+        let (tranches, tranche_count) = lp_unlock_tranches(now, &unlock_schedule)?;
+        let lock_until = if burn_lp { 0 } else { tranches[tranche_count as usize - 1].unlock_time };
+        let token_lock = &mut ctx.accounts.lp_token_lock;
+        token_lock.owner = ctx.accounts.lp_lock.key();
+        token_lock.beneficiary = ctx.accounts.lp_lock.key(); // released through release_lp
+        token_lock.mint = ctx.accounts.lp_mint.key();
+        token_lock.vault = ctx.accounts.lp_lock_vault.key();
+        token_lock.id = LP_TOKEN_LOCK_ID;
+        token_lock.deposited = if burn_lp { 0 } else { lp_amount };
+        token_lock.released = 0;
+        token_lock.start_time = now;
+        token_lock.cliff_time = if burn_lp { now } else { tranches[0].unlock_time };
+        token_lock.end_time = if burn_lp { now } else { lock_until };
+        token_lock.bump = ctx.bumps.lp_token_lock;
+
+        if burn_lp && lp_amount > 0 {
+            let lp_lock_key = ctx.accounts.lp_lock.key();
+            let id_bytes = LP_TOKEN_LOCK_ID.to_le_bytes();
+            let lock_seeds: &[&[u8]] =
+                &[b"token-lock", lp_lock_key.as_ref(), &id_bytes, &[ctx.bumps.lp_token_lock]];
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.lp_mint.to_account_info(),
+                        from: ctx.accounts.lp_lock_vault.to_account_info(),
+                        authority: ctx.accounts.lp_token_lock.to_account_info(),
+                    },
+                    &[lock_seeds],
+                ),
+                lp_amount,
+            )?;
+        }
+
+        let lock = &mut ctx.accounts.lp_lock;
+        lock.amm_id = ctx.accounts.amm_id.key();
+        lock.launch = ctx.accounts.launch_config.key();
+        lock.current_amm = ctx.accounts.amm_id.key();
+        lock.token_lock = ctx.accounts.lp_token_lock.key();
+        lock.migration_allowed = false;
+        lock.migration_target = Pubkey::default();
+        lock.migration_proposed_at = 0;
+        lock.position_mint = ctx.accounts.position_mint.key();
+        lock.tranches = tranches;
+        lock.tranche_count = if burn_lp { 0 } else { tranche_count };
+        lock.burned = burn_lp;
+        lock.bump = ctx.bumps.lp_lock;
+
+        // 1-of-1 position token: mint to the payer, then drop the mint authority
+        let amm_key = ctx.accounts.amm_id.key();
+        let lp_lock_seeds: &[&[u8]] = &[b"lp-lock", amm_key.as_ref(), &[ctx.bumps.lp_lock]];
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.position_mint.to_account_info(),
+                    to: ctx.accounts.position_ata.to_account_info(),
+                    authority: ctx.accounts.lp_lock.to_account_info(),
+                },
+                &[lp_lock_seeds],
+            ),
+            1,
+        )?;
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.lp_lock.to_account_info(),
+                    account_or_mint: ctx.accounts.position_mint.to_account_info(),
+                },
+                &[lp_lock_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let record = &mut ctx.accounts.graduation_record;
+        record.launch = ctx.accounts.launch_config.key();
+        record.mint = ctx.accounts.mint.key();
+        record.amm_id = ctx.accounts.amm_id.key();
+        record.lp_mint = ctx.accounts.lp_mint.key();
+        record.lp_amount = lp_amount;
+        record.token_amount = token_half;
+        record.usdc_amount = usdc_half;
+        record.liquidity_burned = burn_lp;
+        record.lock_until = lock_until;
+        record.graduated_at = now;
+        record.bump = ctx.bumps.graduation_record;
+
+        let config = &mut ctx.accounts.launch_config;
+        let launch = config.key();
+        config.transition(launch, LaunchPhase::Graduated)?;
+        config.graduated_at = now;
+
+        emit!(MigratedToAMMEvent {
+            launch,
+            token_amount: token_half,
+            usdc_amount: usdc_half,
+        });
+        if burn_lp {
+            emit!(LiquidityBurned {
+                launch,
+                amm_id: ctx.accounts.amm_id.key(),
+                lp_mint: ctx.accounts.lp_mint.key(),
+                lp_amount,
+                token_amount: token_half,
+                usdc_amount: usdc_half,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Position holder pushes every tranche that has not unlocked yet `additional_secs` further out.
+    pub fn extend_lock(ctx: Context<ExtendLock>, additional_secs: i64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let lock = &mut ctx.accounts.lock;
+        require!(!lock.burned, LaunchError::LiquidityBurned);
+        require!(additional_secs > 0, LaunchError::InvalidLock);
+        let count = lock.tranche_count as usize;
+        for t in lock.tranches[..count].iter_mut().filter(|t| t.unlock_time > now) {
+            t.unlock_time = t.unlock_time.saturating_add(additional_secs);
+        }
+        let final_unlock = lock.final_unlock();
+        let token_lock = &mut ctx.accounts.token_lock;
+        require!(final_unlock - token_lock.start_time <= MAX_LOCK_DURATION, LaunchError::InvalidLock);
+        token_lock.cliff_time = token_lock.cliff_time.max(lock.tranches[0].unlock_time);
+        token_lock.end_time = final_unlock;
+
+        emit!(LpLockExtendedEvent { lp_lock: lock.key(), additional_secs, final_unlock });
+        Ok(())
+    }
+
+    pub fn release_lp(ctx: Context<ReleaseLp>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let token_lock = &mut ctx.accounts.token_lock;
+        let amount = ctx
+            .accounts
+            .lock
+            .unlocked(token_lock.deposited, now)
+            .saturating_sub(token_lock.released);
+        require!(amount > 0, LaunchError::StillLocked);
+        token_lock.released = token_lock.released.saturating_add(amount);
+
+        let owner = token_lock.owner;
+        let id_bytes = token_lock.id.to_le_bytes();
+        let lock_seeds: &[&[u8]] = &[b"token-lock", owner.as_ref(), &id_bytes, &[token_lock.bump]];
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.lp_vault.to_account_info(),
+                    to: ctx.accounts.holder_lp_ata.to_account_info(),
+                    authority: token_lock.to_account_info(),
+                },
+                &[lock_seeds],
+            ),
+            amount,
+        )?;
+
+        emit!(LockReleasedEvent {
+            lock: token_lock.key(),
+            beneficiary: ctx.accounts.holder.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    // Read-only: simulate to check that nobody can mint this token anymore.
+    pub fn supply_proof(ctx: Context<SupplyProofView>) -> Result<SupplyProof> {
+        let mint = &ctx.accounts.mint;
+        let mint_authority_revoked = mint.mint_authority.is_none();
+        Ok(SupplyProof {
+            mint: mint.key(),
+            supply: mint.supply,
+            total_supply: ctx.accounts.launch_config.total_supply,
+            mint_authority_revoked,
+            capped: mint_authority_revoked && ctx.accounts.launch_config.supply_finalized,
+        })
+    }
+
+    pub fn set_lp_fee_config(
+        ctx: Context<SetLpFeeConfig>,
+        amm_adapter: Pubkey,
+        owner_bps: u16,
+        holders_bps: u16,
+    ) -> Result<()> {
+        require!(owner_bps as u32 + holders_bps as u32 <= 10_000, LaunchError::InvalidFeeSplit);
+        let platform = &mut ctx.accounts.platform_config;
+        platform.amm_adapter = amm_adapter;
+        platform.lp_fee_owner_bps = owner_bps;
+        platform.lp_fee_holders_bps = holders_bps;
+        emit!(LpFeeConfigUpdatedEvent { amm_adapter, owner_bps, holders_bps });
+        Ok(())
+    }
+
+    // Collects swap fees on the locked LP through the whitelisted AMM adapter, signing as
+    // the LP token lock. The adapter must deliver fees into the lock's USDC/token fee
+    // accounts and leave the LP vault untouched; the gains are then split per PlatformConfig,
+    // with the holders' token-side share indexed separately (claim_lp_token_fees).
+    // remaining_accounts: the adapter's accounts, passed through as-is.
+    pub fn harvest_lp_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HarvestLpFees<'info>>,
+        adapter_data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_WITHDRAWALS)?;
+        let principal = ctx.accounts.lp_vault.amount;
+        let usdc_before = ctx.accounts.fee_usdc_ata.amount;
+        let tokens_before = ctx.accounts.fee_token_ata.amount;
+
+        let token_lock = &ctx.accounts.token_lock;
+        let lock_key = token_lock.key();
+        let id_bytes = token_lock.id.to_le_bytes();
+        let lock_seeds: &[&[u8]] = &[b"token-lock", token_lock.owner.as_ref(), &id_bytes, &[token_lock.bump]];
+
+        let metas = ctx
+            .remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer || a.key() == lock_key,
+                is_writable: a.is_writable,
+            })
+            .collect();
+        invoke_signed(
+            &Instruction { program_id: ctx.accounts.amm_adapter.key(), accounts: metas, data: adapter_data },
+            ctx.remaining_accounts,
+            &[lock_seeds],
+        )?;
+
+        ctx.accounts.lp_vault.reload()?;
+        require!(ctx.accounts.lp_vault.amount >= principal, LaunchError::PrincipalMoved);
+        require_lock_custody(&ctx.accounts.lp_vault, lock_key)?;
+        ctx.accounts.fee_usdc_ata.reload()?;
+        ctx.accounts.fee_token_ata.reload()?;
+        let usdc_gain = ctx.accounts.fee_usdc_ata.amount.saturating_sub(usdc_before);
+        let token_gain = ctx.accounts.fee_token_ata.amount.saturating_sub(tokens_before);
+        require!(usdc_gain > 0 || token_gain > 0, LaunchError::ZeroEntitled);
+
+        let platform = &ctx.accounts.platform_config;
+        let (usdc_owner, usdc_holders, usdc_platform) = platform.split_lp_fees(usdc_gain);
+        let (tokens_owner, tokens_holders, tokens_platform) = platform.split_lp_fees(token_gain);
+
+        let usdc_program = ctx.accounts.token_program.to_account_info();
+        let token_program = ctx.accounts.token_2022_program.to_account_info();
+        let lock_ai = ctx.accounts.token_lock.to_account_info();
+        let payouts = [
+            (&usdc_program, &ctx.accounts.fee_usdc_ata, &ctx.accounts.owner_usdc_ata, usdc_owner),
+            (&usdc_program, &ctx.accounts.fee_usdc_ata, &ctx.accounts.usdc_vault, usdc_holders),
+            (&usdc_program, &ctx.accounts.fee_usdc_ata, &ctx.accounts.platform_usdc_ata, usdc_platform),
+            (&token_program, &ctx.accounts.fee_token_ata, &ctx.accounts.owner_x_ata, tokens_owner),
+            (&token_program, &ctx.accounts.fee_token_ata, &ctx.accounts.holders_token_vault, tokens_holders),
+            (&token_program, &ctx.accounts.fee_token_ata, &ctx.accounts.platform_x_ata, tokens_platform),
+        ];
+        for (program, from, to, amount) in payouts {
+            if amount > 0 {
+                token_interface::transfer(
+                    CpiContext::new_with_signer(
+                        program.clone(),
+                        Transfer { from: from.to_account_info(), to: to.to_account_info(), authority: lock_ai.clone() },
+                        &[lock_seeds],
+                    ),
+                    amount,
+                )?;
+            }
+        }
+        ctx.accounts.holders_token_vault.reload()?;
+        let supply = circulating_supply(&ctx.accounts.mint, &ctx.accounts.bonding_curve_ata);
+        let config = &mut ctx.accounts.launch_config;
+        config.holders_reserve_usdc = config.holders_reserve_usdc.saturating_add(usdc_holders);
+        config.accrue_holders_index(usdc_holders, supply);
+        // tokens waiting in the holders vault are nobody's holding yet
+        config.accrue_holders_token_index(
+            tokens_holders,
+            supply.saturating_sub(ctx.accounts.holders_token_vault.amount),
+        );
+
+        emit!(LpFeesHarvestedEvent {
+            lp_lock: ctx.accounts.lock.key(),
+            usdc_owner,
+            usdc_holders,
+            usdc_platform,
+            tokens_owner,
+            tokens_holders,
+            tokens_platform,
+        });
+        Ok(())
+    }
+
+    // LP holder (the lock beneficiary) proposes a new pool; execution needs platform
+    // approval and waits out LP_MIGRATION_TIMELOCK from the proposal.
+    pub fn propose_lp_migration(ctx: Context<ProposeLpMigration>, target: Pubkey) -> Result<()> {
+        let lock = &mut ctx.accounts.lock;
+        require!(!lock.burned, LaunchError::LiquidityBurned);
+        require!(target != Pubkey::default() && target != lock.current_amm, LaunchError::MigrationNotAllowed);
+        let now = Clock::get()?.unix_timestamp;
+        lock.migration_target = target;
+        lock.migration_proposed_at = now;
+        lock.migration_allowed = false;
+        emit!(LpMigrationProposedEvent {
+            lp_lock: lock.key(),
+            target,
+            executable_at: now + LP_MIGRATION_TIMELOCK,
+        });
+        Ok(())
+    }
+
+    pub fn approve_lp_migration(ctx: Context<ApproveLpMigration>) -> Result<()> {
+        let lock = &mut ctx.accounts.lock;
+        require!(lock.migration_proposed_at != 0, LaunchError::NoMigrationProposed);
+        lock.migration_allowed = true;
+        emit!(LpMigrationApprovedEvent {
+            lp_lock: lock.key(),
+            target: lock.migration_target,
+            approver: ctx.accounts.authority.key(),
+        });
+        Ok(())
+    }
+
+    pub fn cancel_lp_migration(ctx: Context<CancelLpMigration>) -> Result<()> {
+        let signer = ctx.accounts.signer.key();
+        let lock = &mut ctx.accounts.lock;
+        let is_holder = ctx
+            .accounts
+            .position_ata
+            .as_ref()
+            .is_some_and(|ata| lock.is_position_holder(ata, signer));
+        require!(
+            is_holder || signer == ctx.accounts.platform_config.authority,
+            LaunchError::NotPositionHolder
+        );
+        require!(lock.migration_proposed_at != 0, LaunchError::NoMigrationProposed);
+        let target = lock.migration_target;
+        lock.migration_target = Pubkey::default();
+        lock.migration_proposed_at = 0;
+        lock.migration_allowed = false;
+        emit!(LpMigrationCancelledEvent { lp_lock: lock.key(), target });
+        Ok(())
+    }
+
+    // Moves the locked LP into the approved pool through the whitelisted AMM adapter, signing
+    // as the LP token lock. The adapter must empty the old vault and deliver the new pool's LP
+    // into the lock's new vault; the schedule carries over scaled to the new LP amount.
+    // remaining_accounts: the adapter's accounts, passed through as-is.
+    pub fn migrate_lp<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateLP<'info>>,
+        adapter_data: Vec<u8>,
+    ) -> Result<()> {
+        let lock = &ctx.accounts.lock;
+        require!(lock.migration_allowed, LaunchError::MigrationNotAllowed);
+        require!(
+            Clock::get()?.unix_timestamp >= lock.migration_proposed_at + LP_MIGRATION_TIMELOCK,
+            LaunchError::MigrationTimelock
+        );
+        require!(ctx.accounts.new_amm.key() == lock.migration_target, LaunchError::MigrationNotAllowed);
+        require!(
+            ctx.accounts.new_lp_lock_vault.key() != ctx.accounts.old_vault.key(),
+            LaunchError::MigrationNotAllowed
+        );
+
+        let amount = ctx.accounts.old_vault.amount;
+        require!(amount > 0, LaunchError::ZeroAmount);
+        let received_before = ctx.accounts.new_lp_lock_vault.amount;
+
+        let token_lock = &ctx.accounts.token_lock;
+        let lock_key = token_lock.key();
+        let owner = token_lock.owner;
+        let id_bytes = token_lock.id.to_le_bytes();
+        let lock_seeds: &[&[u8]] = &[b"token-lock", owner.as_ref(), &id_bytes, &[token_lock.bump]];
+        let metas = ctx
+            .remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer || a.key() == lock_key,
+                is_writable: a.is_writable,
+            })
+            .collect();
+        invoke_signed(
+            &Instruction { program_id: ctx.accounts.amm_adapter.key(), accounts: metas, data: adapter_data },
+            ctx.remaining_accounts,
+            &[lock_seeds],
+        )?;
+
+        ctx.accounts.old_vault.reload()?;
+        ctx.accounts.new_lp_lock_vault.reload()?;
+        require!(ctx.accounts.old_vault.amount == 0, LaunchError::MigrationIncomplete);
+        require_lock_custody(&ctx.accounts.new_lp_lock_vault, lock_key)?;
+        let new_amount = ctx.accounts.new_lp_lock_vault.amount.saturating_sub(received_before);
+        require!(new_amount > 0, LaunchError::MigrationIncomplete);
+
+        // the lock now holds the new pool's LP tokens
+        let token_lock = &mut ctx.accounts.token_lock;
+        token_lock.mint = ctx.accounts.lp_mint.key();
+        token_lock.vault = ctx.accounts.new_lp_lock_vault.key();
+        token_lock.rescale(amount, new_amount);
+
+        let lock = &mut ctx.accounts.lock;
+        let from_amm = lock.current_amm;
+        lock.current_amm = ctx.accounts.new_amm.key();
+        lock.migration_target = Pubkey::default();
+        lock.migration_proposed_at = 0;
+        lock.migration_allowed = false;
+
+        emit!(LpMigratedEvent {
+            lp_lock: lock.key(),
+            from_amm,
+            to_amm: lock.current_amm,
+            amount,
+            new_amount,
+        });
+        Ok(())
+    }
+
+    pub fn create_lock(
+        ctx: Context<CreateLock>,
+        id: u64,
+        beneficiary: Pubkey,
+        amount: u64,
+        cliff_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        require!(amount > 0, LaunchError::ZeroAmount);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= cliff_time && cliff_time <= end_time && end_time - now <= MAX_LOCK_DURATION,
+            LaunchError::InvalidLock
+        );
+
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let lock = &mut ctx.accounts.token_lock;
+        lock.owner = ctx.accounts.owner.key();
+        lock.beneficiary = beneficiary;
+        lock.mint = ctx.accounts.mint.key();
+        lock.vault = ctx.accounts.vault.key();
+        lock.id = id;
+        lock.deposited = amount;
+        lock.released = 0;
+        lock.start_time = now;
+        lock.cliff_time = cliff_time;
+        lock.end_time = end_time;
+        lock.bump = ctx.bumps.token_lock;
+
+        emit!(LockCreatedEvent {
+            lock: lock.key(),
+            owner: lock.owner,
+            beneficiary,
+            mint: lock.mint,
+            amount,
+            cliff_time,
+            end_time,
+        });
+        Ok(())
+    }
+
+    pub fn deposit_lock(ctx: Context<DepositLock>, amount: u64) -> Result<()> {
+        require!(amount > 0, LaunchError::ZeroAmount);
+        token_interface::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner_ata.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let lock = &mut ctx.accounts.token_lock;
+        lock.deposited = lock.deposited.checked_add(amount).ok_or(LaunchError::Overflow)?;
+        emit!(LockDepositEvent { lock: lock.key(), amount });
+        Ok(())
+    }
+
+    pub fn extend_token_lock(ctx: Context<ExtendTokenLock>, cliff_time: i64, end_time: i64) -> Result<()> {
+        let lock = &mut ctx.accounts.token_lock;
+        require!(
+            cliff_time >= lock.cliff_time && end_time >= lock.end_time,
+            LaunchError::LockShortened
+        );
+        require!(
+            cliff_time <= end_time && end_time - lock.start_time <= MAX_LOCK_DURATION,
+            LaunchError::InvalidLock
+        );
+        lock.cliff_time = cliff_time;
+        lock.end_time = end_time;
+        emit!(LockExtendedEvent { lock: lock.key(), cliff_time, end_time });
+        Ok(())
+    }
+
+    pub fn release_lock(ctx: Context<ReleaseLock>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let lock = &mut ctx.accounts.token_lock;
+        let amount = lock.releasable(now);
+        require!(amount > 0, LaunchError::StillLocked);
+        lock.released = lock.released.saturating_add(amount);
+
+        let owner = lock.owner;
+        let id_bytes = lock.id.to_le_bytes();
+        let lock_seeds: &[&[u8]] = &[b"token-lock", owner.as_ref(), &id_bytes, &[lock.bump]];
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.beneficiary_ata.to_account_info(),
+                    authority: lock.to_account_info(),
+                },
+                &[lock_seeds],
+            ),
+            amount,
+        )?;
+
+        emit!(LockReleasedEvent { lock: lock.key(), beneficiary: lock.beneficiary, amount });
+        Ok(())
+    }
+
+    pub fn set_lock_beneficiary(ctx: Context<SetLockBeneficiary>, beneficiary: Pubkey) -> Result<()> {
+        let lock = &mut ctx.accounts.token_lock;
+        let previous = lock.beneficiary;
+        lock.beneficiary = beneficiary;
+        emit!(LockBeneficiaryChangedEvent { lock: lock.key(), previous, beneficiary });
+        Ok(())
+    }
+
+    pub fn withdraw_platform_remaining(ctx: Context<WithdrawPlatformRemaining>) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_WITHDRAWALS)?;
+        let config = &mut ctx.accounts.launch_config;
+        config.require_phase(&POST_START_PHASES)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now - config.last_withdraw >= 24 * 60 * 60, LaunchError::TooEarly); // 24h cool-down
+        let available = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
+        require!(available > 0, LaunchError::ZeroEntitled);
+        require!(available < config.auto_withdraw_threshold, LaunchError::AboveAutoThreshold);
+
+        let reserved = config.holders_reserve_usdc.saturating_add(config.creator_reserve_usdc);
+        let vault_bal = ctx.accounts.usdc_vault.amount;
+        let free_liquidity = vault_bal.saturating_sub(reserved);
+        let amount = core::cmp::min(available, free_liquidity);
+        require!(amount > 0, LaunchError::ZeroEntitled);
+
+        config.platform_auto_transferred = config.platform_auto_transferred.saturating_add(amount);
+        config.last_withdraw = now;
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.platform_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            amount,
+        )?;
+        config.virtual_usdc = config.virtual_usdc.saturating_sub(amount);
+        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+
+        emit!(PlatformWithdrawnEvent { launch: ctx.accounts.launch_config.key(), amount });
+        Ok(())
+    }
+    pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.launch_config;
+        require!(now > config.deadline, LaunchError::TooEarly);
+        let launch = config.key();
+        // nobody may have traded since the opening phase lapsed
+        let phase_before = config.phase;
+        config.sync_phase(launch, now)?;
+
+        let total_raised = config.total_raised;
+        let bond_missing = config.has_bond() && !config.bond_posted;
+        match config.phase {
+            LaunchPhase::Curve if total_raised >= 5_000_000_000 && !bond_missing => {
+                config.transition(launch, LaunchPhase::Succeeded)?;
+                emit!(LaunchSucceededEvent { launch, total_raised });
+                return Ok(());
+            }
+            LaunchPhase::Curve | LaunchPhase::BatchAuction | LaunchPhase::Lottery => {
+                config.void_opening_sale();
+                config.transition(launch, LaunchPhase::Failed)?;
+                emit!(LaunchFailedEvent { launch, total_raised });
+            }
+            // sync_phase just closed a round that missed its soft cap
+            LaunchPhase::Failed if phase_before != LaunchPhase::Failed => {}
+            _ => return err!(LaunchError::WrongPhase),
+        }
+
+        let creator_refund = config.creator_paid_usdc;
+        let available = ctx.accounts.usdc_vault.amount;
+        let refund = core::cmp::min(creator_refund, available);
+        if refund > 0 {
+            token_interface::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.usdc_vault.to_account_info(),
+                        to: ctx.accounts.creator_usdc_ata.to_account_info(),
+                        authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                    },
+                    &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+                ),
+                refund,
+            )?;
+            config.creator_paid_usdc = config.creator_paid_usdc.saturating_sub(refund);
+            config.virtual_usdc = config.virtual_usdc.saturating_sub(refund);
+            config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+        }
+        Ok(())
+    }
+
+    pub fn withdraw_creator_reserve(ctx: Context<WithdrawCreatorReserve>, requested: u64) -> Result<()> {
+        ctx.accounts.platform_config.require_unpaused(&ctx.accounts.launch_config, PAUSE_WITHDRAWALS)?;
+        let cfg = &mut ctx.accounts.launch_config;
+        cfg.require_phase(&POST_START_PHASES)?;
+        let reserved = cfg.creator_reserve_usdc;
+        require!(reserved > 0, LaunchError::ZeroEntitled);
+        let platform_reserved = cfg.platform_fees_collected.saturating_sub(cfg.platform_auto_transferred);
+        let holders_reserved = cfg.holders_reserve_usdc;
+        let vault_bal = ctx.accounts.usdc_vault.amount;
+        let free_liquidity = vault_bal
+            .saturating_sub(platform_reserved)
+            .saturating_sub(holders_reserved);
+        let amount = core::cmp::min(requested, core::cmp::min(reserved, free_liquidity));
+        require!(amount > 0, LaunchError::ZeroEntitled);
+
+        cfg.creator_reserve_usdc = cfg.creator_reserve_usdc.saturating_sub(amount);
+
+        token_interface::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.usdc_vault.to_account_info(),
+                    to: ctx.accounts.creator_usdc_ata.to_account_info(),
+                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
+                },
+                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
+            ),
+            amount,
+        )?;
+        cfg.virtual_usdc = cfg.virtual_usdc.saturating_sub(amount);
+        cfg.k = (cfg.virtual_usdc as u128) * (cfg.virtual_token as u128);
+        Ok(())
+    }
+}
+
+// ------------------- Accounts -------------------
+
+#[derive(Accounts)]
+#[instruction(_usdc_mint: Pubkey, virtual_usdc_amount: u64, params: LaunchParams)]
+pub struct CreateToken<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + TickerRecord::INIT_SPACE,
+        seeds = [b"ticker", normalize_ticker(&params.metadata.symbol).as_bytes()],
+        bump,
+    )]
+    pub ticker_record: Box<Account<'info, TickerRecord>>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = mint_auth,
+        mint::token_program = token_2022_program,
+        extensions::metadata_pointer::authority = mint_auth,
+        extensions::metadata_pointer::metadata_address = mint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA mint authority (scoped by mint)
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + LaunchConfig::INIT_SPACE,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: vault authority PDA (scoped by mint)
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: vesting vault authority PDA (scoped by mint)
+    #[account(seeds = [b"vesting", mint.key().as_ref()], bump)]
+    pub vesting_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet constant)
+    #[account(mut, address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: platform wallet (SystemAccount)
+    #[account(mut, address = PLATFORM_WALLET_PUBKEY)]
+    pub platform_wallet: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = platform_wallet,
+    )]
+    pub platform_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPlatformConfig<'info> {
+    #[account(mut, address = PLATFORM_WALLET_PUBKEY)]
+    pub platform: Signer<'info>,
+
+    #[account(
+        init,
+        payer = platform,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [b"platform"],
+        bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformPause<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = guardian,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct SetLaunchPause<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        has_one = guardian,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct WindDown<'info> {
+    pub guardian: Signer<'info>,
+
+    #[account(
+        has_one = guardian,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+    )]
+    pub holder_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub vesting_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: vesting vault authority PDA
+    #[account(seeds = [b"vesting", mint.key().as_ref()], bump)]
+    pub vesting_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub beneficiary_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PostBond<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: USDC or the launch mint, per launch_config
+    #[account(address = launch_config.bond_mint(mint.key()))]
+    pub bond_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub creator_bond_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = bond_mint,
+        associated_token::authority = bond_vault_auth,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: bond vault authority PDA
+    #[account(seeds = [b"bond-vault", mint.key().as_ref()], bump)]
+    pub bond_vault_auth: UncheckedAccount<'info>,
+
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(
+        seeds = [b"buyer_ledger", mint.key().as_ref(), launch_config.creator.as_ref()],
+        bump = creator_ledger.bump,
+    )]
+    pub creator_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC or the launch mint, per launch_config
+    #[account(address = launch_config.bond_mint(mint.key()))]
+    pub bond_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = bond_vault_auth,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: bond vault authority PDA
+    #[account(seeds = [b"bond-vault", mint.key().as_ref()], bump)]
+    pub bond_vault_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseBond<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(
+        seeds = [b"buyer_ledger", mint.key().as_ref(), creator.key().as_ref()],
+        bump = creator_ledger.bump,
+    )]
+    pub creator_ledger: Box<Account<'info, BuyerLedger>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: USDC or the launch mint, per launch_config
+    #[account(address = launch_config.bond_mint(mint.key()))]
+    pub bond_mint: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = bond_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub creator_bond_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = bond_mint,
+        associated_token::authority = bond_vault_auth,
+        associated_token::token_program = bond_token_program,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: bond vault authority PDA
+    #[account(seeds = [b"bond-vault", mint.key().as_ref()], bump)]
+    pub bond_vault_auth: UncheckedAccount<'info>,
+
+    pub bond_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMerkleRoot<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = creator,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        has_one = creator,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(mut, mint::token_program = token_2022_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: mint authority PDA, also the metadata update authority
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseTicker<'info> {
+    #[account(
+        mut,
+        seeds = [b"ticker", ticker_record.symbol.as_bytes()],
+        bump = ticker_record.bump,
+        constraint = ticker_record.launch == launch_config.key() @ LaunchError::TickerNotReleasable,
+    )]
+    pub ticker_record: Box<Account<'info, TickerRecord>>,
+
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+}
+
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct AssignTicker<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority, seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TickerRecord::INIT_SPACE,
+        seeds = [b"ticker", normalize_ticker(&symbol).as_bytes()],
+        bump,
+    )]
+    pub ticker_record: Box<Account<'info, TickerRecord>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterBuyer<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    /// CHECK: burn authority PDA (same as mint_auth)
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub burn_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = burn_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub burn_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, required when the launch enables the sandwich guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + SlotActivity::INIT_SPACE,
+        seeds = [b"slot-activity", mint.key().as_ref()],
+        bump,
+    )]
+    pub slot_activity: Option<Box<Account<'info, SlotActivity>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + BuyCommitment::INIT_SPACE,
+        seeds = [b"buy-commit", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub commitment: Box<Account<'info, BuyCommitment>>,
+
+    #[account(mut)]
+    pub buyer_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = commit_escrow_auth,
+    )]
+    pub commit_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: commit escrow authority PDA
+    #[account(seeds = [b"commit-escrow", mint.key().as_ref()], bump)]
+    pub commit_escrow_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealBuy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"buy-commit", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = commitment.bump,
+        constraint = commitment.buyer == buyer.key() @ LaunchError::NotYourLedger,
+        close = buyer,
+    )]
+    pub commitment: Box<Account<'info, BuyCommitment>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = commit_escrow_auth,
+    )]
+    pub commit_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: commit escrow authority PDA
+    #[account(seeds = [b"commit-escrow", mint.key().as_ref()], bump)]
+    pub commit_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer,
+    )]
+    pub buyer_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    /// CHECK: mint authority PDA, also owns the burn ATA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = mint_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub burn_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimCommitment<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"buy-commit", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = commitment.bump,
+        constraint = commitment.buyer == buyer.key() @ LaunchError::NotYourLedger,
+        close = buyer,
+    )]
+    pub commitment: Box<Account<'info, BuyCommitment>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = commit_escrow_auth,
+    )]
+    pub commit_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: commit escrow authority PDA
+    #[account(seeds = [b"commit-escrow", mint.key().as_ref()], bump)]
+    pub commit_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = usdc_mint,
+        token::authority = buyer,
+    )]
+    pub buyer_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), trader.key().as_ref()],
+        bump,
+    )]
+    pub trader_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"order-book", mint.key().as_ref()],
+        bump,
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    // USDC for buys, launch tokens for sells
+    #[account(mut, token::authority = trader)]
+    pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(constraint = destination.owner == trader.key() @ LaunchError::BadDestination)]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = batch_escrow_auth,
+    )]
+    pub batch_usdc_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = trader,
+        associated_token::mint = mint,
+        associated_token::authority = batch_escrow_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub batch_token_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: batch escrow authority PDA
+    #[account(seeds = [b"batch-escrow", mint.key().as_ref()], bump)]
+    pub batch_escrow_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    pub trader: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, seeds = [b"order-book", mint.key().as_ref()], bump = order_book.bump)]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(mut, constraint = refund_account.owner == trader.key() @ LaunchError::BadDestination)]
+    pub refund_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = batch_escrow_auth,
+    )]
+    pub batch_usdc_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = batch_escrow_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub batch_token_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: batch escrow authority PDA
+    #[account(seeds = [b"batch-escrow", mint.key().as_ref()], bump)]
+    pub batch_escrow_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [b"order-book", mint.key().as_ref()], bump = order_book.bump)]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = batch_escrow_auth,
+    )]
+    pub batch_usdc_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = batch_escrow_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub batch_token_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: batch escrow authority PDA
+    #[account(seeds = [b"batch-escrow", mint.key().as_ref()], bump)]
+    pub batch_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBatchRefund<'info> {
+    pub trader: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), trader.key().as_ref()],
+        bump = trader_ledger.bump,
+    )]
+    pub trader_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(mut, token::mint = usdc_mint, token::authority = trader)]
+    pub usdc_refund_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = mint, token::authority = trader, token::token_program = token_2022_program)]
+    pub token_refund_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = batch_escrow_auth,
+    )]
+    pub batch_usdc_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = batch_escrow_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub batch_token_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: batch escrow authority PDA
+    #[account(seeds = [b"batch-escrow", mint.key().as_ref()], bump)]
+    pub batch_escrow_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct BuyFixedPrice<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub buyer_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFixedPriceTokens<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = buyer_ledger.bump,
+        constraint = buyer_ledger.buyer == buyer.key() @ LaunchError::NotYourLedger,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundFixedPrice<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        bump = buyer_ledger.bump,
+        constraint = buyer_ledger.buyer == buyer.key() @ LaunchError::NotYourLedger,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + AuctionBook::INIT_SPACE,
+        seeds = [b"auction-book", mint.key().as_ref()],
+        bump,
+    )]
+    pub auction_book: Box<Account<'info, AuctionBook>>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + AuctionBid::INIT_SPACE,
+        seeds = [b"auction-bid", mint.key().as_ref(), bidder.key().as_ref()],
+        bump,
+    )]
+    pub bid: Box<Account<'info, AuctionBid>>,
+
+    #[account(mut)]
+    pub bidder_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = auction_escrow_auth,
+    )]
+    pub auction_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: auction escrow authority PDA
+    #[account(seeds = [b"auction-escrow", mint.key().as_ref()], bump)]
+    pub auction_escrow_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // absent when nobody bid
+    #[account(seeds = [b"auction-book", mint.key().as_ref()], bump = auction_book.bump)]
+    pub auction_book: Option<Box<Account<'info, AuctionBook>>>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = auction_escrow_auth,
+    )]
+    pub auction_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: auction escrow authority PDA
+    #[account(seeds = [b"auction-escrow", mint.key().as_ref()], bump)]
+    pub auction_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAuction<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"auction-bid", mint.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        constraint = bid.bidder == bidder.key() @ LaunchError::NotYourLedger,
+    )]
+    pub bid: Box<Account<'info, AuctionBid>>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bidder_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = auction_escrow_auth,
+    )]
+    pub auction_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: auction escrow authority PDA
+    #[account(seeds = [b"auction-escrow", mint.key().as_ref()], bump)]
+    pub auction_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = bidder,
+    )]
+    pub bidder_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnterLottery<'info> {
+    #[account(mut)]
+    pub entrant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = entrant,
+        space = 8 + LotteryTicket::INIT_SPACE,
+        seeds = [b"lottery-ticket", mint.key().as_ref(), entrant.key().as_ref()],
+        bump,
+    )]
+    pub ticket: Box<Account<'info, LotteryTicket>>,
+
+    #[account(mut)]
+    pub entrant_usdc: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = entrant,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = lottery_escrow_auth,
+    )]
+    pub lottery_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: lottery escrow authority PDA
+    #[account(seeds = [b"lottery-escrow", mint.key().as_ref()], bump)]
+    pub lottery_escrow_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitLotteryDraw<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct DrawLottery<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: SlotHashes sysvar, parsed manually (too large to deserialize)
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = lottery_escrow_auth,
+    )]
+    pub lottery_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: lottery escrow authority PDA
+    #[account(seeds = [b"lottery-escrow", mint.key().as_ref()], bump)]
+    pub lottery_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLottery<'info> {
+    #[account(mut)]
+    pub entrant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery-ticket", mint.key().as_ref(), entrant.key().as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.entrant == entrant.key() @ LaunchError::NotYourLedger,
+        close = entrant,
+    )]
+    pub ticket: Box<Account<'info, LotteryTicket>>,
+
+    #[account(
+        init_if_needed,
+        payer = entrant,
+        associated_token::mint = mint,
+        associated_token::authority = entrant,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub entrant_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = lottery_escrow_auth,
+    )]
+    pub lottery_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: lottery escrow authority PDA
+    #[account(seeds = [b"lottery-escrow", mint.key().as_ref()], bump)]
+    pub lottery_escrow_auth: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = entrant,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = entrant,
+    )]
+    pub entrant_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: mint authority PDA
+    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
+    pub mint_auth: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Sell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_ledger: Box<Account<'info, BuyerLedger>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub seller_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        let lock = &mut ctx.accounts.lp_lock;
-        lock.amm_id = ctx.accounts.amm_id.key();
-        lock.lp_mint = ctx.accounts.lp_mint.key();
-        lock.vault_ata = ctx.accounts.lp_lock_vault.key();
-        lock.unlock_timestamp = Clock::get()?.unix_timestamp + LOCK_DURATION;
-        lock.migration_allowed = false;
-        lock.migration_target = Pubkey::default();
-        lock.authority = ctx.accounts.payer.key();
-        lock.bump = ctx.bumps.lp_lock;
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        let config = &mut ctx.accounts.launch_config;
-        config.migrated = true;
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
 
-        emit!(MigratedToAMMEvent {
-            launch: ctx.accounts.launch_config.key(),
-            token_amount: token_half,
-            usdc_amount: usdc_half,
-        });
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = seller,
+    )]
+    pub seller_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        Ok(())
-    }
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
 
-    pub fn migrate_lp(ctx: Context<MigrateLP>) -> Result<()> {
-        let lock = &mut ctx.accounts.lock;
-        require!(lock.migration_allowed, LaunchError::MigrationNotAllowed);
-        require!(Clock::get()?.unix_timestamp >= lock.unlock_timestamp, LaunchError::StillLocked);
-        require!(ctx.accounts.authority.key() == lock.authority, LaunchError::NotYourLedger);
+    /// CHECK: platform wallet (SystemAccount)
+    #[account(mut, address = PLATFORM_WALLET_PUBKEY)]
+    pub platform_wallet: SystemAccount<'info>,
 
-        let amount = ctx.accounts.old_vault.amount;
-        let lock_bump = ctx.bumps.lp_lock_auth;
-        let vault_seeds: &[&[u8]; 3] = &[b"lp-lock", lock.amm_id.as_ref(), &[lock_bump]];
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.old_vault.to_account_info(),
-                    to: ctx.accounts.new_vault.to_account_info(),
-                    authority: ctx.accounts.lp_lock_auth.to_account_info(),
-                },
-                &[vault_seeds],
-            ),
-            amount,
-        )?;
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = platform_wallet,
+    )]
+    pub platform_profit_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        lock.amm_id = ctx.accounts.new_amm.key();
-        lock.vault_ata = ctx.accounts.new_vault.key();
-        lock.migration_target = ctx.accounts.new_amm.key();
-        lock.migration_allowed = false;
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = launch_config,
+    )]
+    pub creator_profit_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        Ok(())
-    }
+    /// CHECK: Instructions sysvar, required when the launch enables the sandwich guard
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
 
-    pub fn withdraw_platform_remaining(ctx: Context<WithdrawPlatformRemaining>) -> Result<()> {
-        let config = &mut ctx.accounts.launch_config;
-        let now = Clock::get()?.unix_timestamp;
-        require!(now - config.last_withdraw >= 24 * 60 * 60, LaunchError::TooEarly); // 24h cool-down
-        let available = config.platform_fees_collected.saturating_sub(config.platform_auto_transferred);
-        require!(available > 0, LaunchError::ZeroEntitled);
-        require!(available < config.auto_withdraw_threshold, LaunchError::AboveAutoThreshold);
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + SlotActivity::INIT_SPACE,
+        seeds = [b"slot-activity", mint.key().as_ref()],
+        bump,
+    )]
+    pub slot_activity: Option<Box<Account<'info, SlotActivity>>>,
 
-        let reserved = config.holders_reserve_usdc.saturating_add(config.creator_reserve_usdc);
-        let vault_bal = ctx.accounts.usdc_vault.amount;
-        let free_liquidity = vault_bal.saturating_sub(reserved);
-        let amount = core::cmp::min(available, free_liquidity);
-        require!(amount > 0, LaunchError::ZeroEntitled);
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
-        config.platform_auto_transferred = config.platform_auto_transferred.saturating_add(amount);
-        config.last_withdraw = now;
+#[derive(Accounts)]
+pub struct ClaimProfits<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.usdc_vault.to_account_info(),
-                    to: ctx.accounts.platform_usdc_ata.to_account_info(),
-                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                },
-                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-            ),
-            amount,
-        )?;
-        config.virtual_usdc = config.virtual_usdc.saturating_sub(amount);
-        config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-        emit!(PlatformWithdrawnEvent { launch: ctx.accounts.launch_config.key(), amount: amount });
-        Ok(())
-    }
-pub fn finalize(ctx: Context<Finalize>) -> Result<()> {
-        let config = &mut ctx.accounts.launch_config;
-        require!(!config.closed, LaunchError::NotEnded);
-        let now = Clock::get()?.unix_timestamp;
-        require!(now > config.deadline, LaunchError::TooEarly);
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
 
-        let total_raised = config.total_raised;
-        if total_raised < 5_000_000_000 {
-            config.failed = true;
-            config.closed = true;
-            emit!(LaunchFailedEvent { launch: config.key(), total_raised });
-            let creator_refund = config.creator_paid_usdc;
-            let available = ctx.accounts.usdc_vault.amount;
-            let refund = core::cmp::min(creator_refund, available);
-            if refund > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.usdc_vault.to_account_info(),
-                            to: ctx.accounts.creator_usdc_ata.to_account_info(),
-                            authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                        },
-                        &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-                    ),
-                    refund,
-                )?;
-                config.creator_paid_usdc = config.creator_paid_usdc.saturating_sub(refund);
-                config.virtual_usdc = config.virtual_usdc.saturating_sub(refund);
-                config.k = (config.virtual_usdc as u128) * (config.virtual_token as u128);
-            }
-        } else {
-            config.failed = false;
-            config.closed = true;
-            emit!(LaunchSucceededEvent { launch: config.key(), total_raised });
-        }
-        Ok(())
-    }
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
 
-    pub fn withdraw_creator_reserve(ctx: Context<WithdrawCreatorReserve>, requested: u64) -> Result<()> {
-        let cfg = &mut ctx.accounts.launch_config;
-        let reserved = cfg.creator_reserve_usdc;
-        require!(reserved > 0, LaunchError::ZeroEntitled);
-        let platform_reserved = cfg.platform_fees_collected.saturating_sub(cfg.platform_auto_transferred);
-        let holders_reserved = cfg.holders_reserve_usdc;
-        let vault_bal = ctx.accounts.usdc_vault.amount;
-        let free_liquidity = vault_bal
-            .saturating_sub(platform_reserved)
-            .saturating_sub(holders_reserved);
-        let amount = core::cmp::min(requested, core::cmp::min(reserved, free_liquidity));
-        require!(amount > 0, LaunchError::ZeroEntitled);
+    pub mint: InterfaceAccount<'info, Mint>,
 
-        cfg.creator_reserve_usdc = cfg.creator_reserve_usdc.saturating_sub(amount);
+    #[account(mut)]
+    pub user_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.usdc_vault.to_account_info(),
-                    to: ctx.accounts.creator_usdc_ata.to_account_info(),
-                    authority: ctx.accounts.usdc_vault_auth.to_account_info(),
-                },
-                &[&[b"usdc-vault", ctx.accounts.mint.key().as_ref(), &[ctx.bumps.usdc_vault_auth]]],
-            ),
-            amount,
-        )?;
-        cfg.virtual_usdc = cfg.virtual_usdc.saturating_sub(amount);
-        cfg.k = (cfg.virtual_usdc as u128) * (cfg.virtual_token as u128);
-        Ok(())
-    }
-}
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+    )]
+    pub user_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-// ------------------- Accounts -------------------
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
 #[derive(Accounts)]
-pub struct CreateToken<'info> {
+pub struct ClaimLpTokenFees<'info> {
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub user: Signer<'info>,
 
     #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = mint_auth,
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
     )]
-    pub mint: Account<'info, Mint>,
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-    /// CHECK: PDA mint authority (scoped by mint)
-    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
-    pub mint_auth: UncheckedAccount<'info>,
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
 
     #[account(
-        init,
-        payer = creator,
-        space = 8 + LaunchConfig::INIT_SPACE,
-        seeds = [b"launch", mint.key().as_ref()],
+        init_if_needed,
+        payer = user,
+        space = 8 + BuyerLedger::INIT_SPACE,
+        seeds = [b"buyer_ledger", mint.key().as_ref(), user.key().as_ref()],
         bump,
     )]
-    pub launch_config: Box<Account<'info, LaunchConfig>>,
+    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        init,
-        payer = creator,
+        mut,
         associated_token::mint = mint,
-        associated_token::authority = launch_config,
+        associated_token::authority = user,
+        associated_token::token_program = token_2022_program,
     )]
-    pub bonding_curve_ata: Box<Account<'info, TokenAccount>>,
+    pub user_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
-        init,
-        payer = creator,
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = usdc_vault_auth,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub holders_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Finalize<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+    )]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: creator from config
+    #[account(address = launch_config.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = caller,
         associated_token::mint = usdc_mint,
-        associated_token::authority = usdc_vault_auth,
+        associated_token::authority = creator,
     )]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: vault authority PDA (scoped by mint)
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// USDC mint (devnet constant)
-    #[account(mut, address = USDC_DEVNET)]
-    pub usdc_mint: Account<'info, Mint>,
+#[derive(Accounts)]
+pub struct ReclaimVirtualFunds<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
 
     #[account(
         mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = creator,
+        has_one = creator,
+        seeds = [b"launch", mint.key().as_ref()],
+        bump = launch_config.bump,
+        constraint = matches!(launch_config.phase, LaunchPhase::Failed | LaunchPhase::Graduated) @ LaunchError::WrongPhase,
     )]
-    pub creator_usdc: Box<Account<'info, TokenAccount>>,
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-    /// CHECK: platform wallet (SystemAccount)
-    #[account(mut, address = PLATFORM_WALLET_PUBKEY)]
-    pub platform_wallet: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// USDC mint (devnet)
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
         payer = creator,
         associated_token::mint = usdc_mint,
-        associated_token::authority = platform_wallet,
+        associated_token::authority = creator,
     )]
-    pub platform_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -915,144 +7008,187 @@ pub struct CreateToken<'info> {
 }
 
 #[derive(Accounts)]
-pub struct Buy<'info> {
+pub struct CloseAndMigrateToRaydium<'info> {
     #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub payer: Signer<'info>,
 
     #[account(
         mut,
+        constraint = launch_config.creator == payer.key(),
         seeds = [b"launch", mint.key().as_ref()],
         bump = launch_config.bump,
+        constraint = launch_config.phase == LaunchPhase::Succeeded @ LaunchError::WrongPhase,
     )]
     pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: USDC devnet constant
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC vault authority PDA
+    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
+    pub usdc_vault_auth: UncheckedAccount<'info>,
+
+    /// CHECK: AMM ID for Raydium pool
+    #[account(mut)]
+    pub amm_id: UncheckedAccount<'info>,
 
     #[account(mut)]
-    pub buyer_usdc: Box<Account<'info, TokenAccount>>,
+    pub lp_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    pub coin_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub bonding_curve_ata: Box<Account<'info, TokenAccount>>,
+    pub pc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
-        associated_token::mint = mint,
-        associated_token::authority = buyer,
+        init,
+        payer = payer,
+        space = 8 + LPLock::INIT_SPACE,
+        seeds = [b"lp-lock", amm_id.key().as_ref()],
+        bump,
     )]
-    pub buyer_x_ata: Box<Account<'info, TokenAccount>>,
+    pub lp_lock: Box<Account<'info, LPLock>>,
 
     #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + BuyerLedger::INIT_SPACE,
-        seeds = [b"buyer_ledger", mint.key().as_ref(), buyer.key().as_ref()],
+        init,
+        payer = payer,
+        space = 8 + GraduationRecord::INIT_SPACE,
+        seeds = [b"graduation", mint.key().as_ref()],
         bump,
     )]
-    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+    pub graduation_record: Box<Account<'info, GraduationRecord>>,
 
-    /// CHECK: burn authority PDA (same as mint_auth)
-    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
-    pub burn_auth: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = lp_lock,
+        seeds = [b"lp-position", lp_lock.key().as_ref()],
+        bump,
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = position_mint,
+        associated_token::authority = payer,
+    )]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenLock::INIT_SPACE,
+        seeds = [b"token-lock", lp_lock.key().as_ref(), &LP_TOKEN_LOCK_ID.to_le_bytes()],
+        bump,
+    )]
+    pub lp_token_lock: Box<Account<'info, TokenLock>>,
 
     #[account(
         init_if_needed,
-        payer = buyer,
-        associated_token::mint = mint,
-        associated_token::authority = burn_auth,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_token_lock,
     )]
-    pub burn_ata: Box<Account<'info, TokenAccount>>,
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: mint authority PDA
     #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
     pub mint_auth: UncheckedAccount<'info>,
 
-    /// CHECK: vault authority PDA
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
-
-    /// CHECK: creator account from config
+    /// CHECK: creator from config
     #[account(address = launch_config.creator)]
     pub creator: UncheckedAccount<'info>,
 
     #[account(
         init_if_needed,
-        payer = buyer,
+        payer = payer,
         associated_token::mint = usdc_mint,
         associated_token::authority = creator,
     )]
-    pub creator_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Sell<'info> {
-    #[account(mut)]
-    pub seller: Signer<'info>,
+pub struct SupplyProofView<'info> {
+    #[account(seeds = [b"launch", mint.key().as_ref()], bump = launch_config.bump)]
+    pub launch_config: Box<Account<'info, LaunchConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    pub holder: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"launch", mint.key().as_ref()],
-        bump = launch_config.bump,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
+        has_one = token_lock,
     )]
-    pub launch_config: Box<Account<'info, LaunchConfig>>,
-
-    pub mint: Account<'info, Mint>,
+    pub lock: Box<Account<'info, LPLock>>,
 
     #[account(mut)]
-    pub seller_x_ata: Box<Account<'info, TokenAccount>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
 
-    #[account(mut)]
-    pub bonding_curve_ata: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = lock.is_position_holder(&position_ata, holder.key()) @ LaunchError::NotPositionHolder)]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+}
 
+#[derive(Accounts)]
+pub struct ReleaseLp<'info> {
     #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
-
-    /// CHECK: USDC vault authority PDA
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
+    pub holder: Signer<'info>,
 
     #[account(
-        init_if_needed,
-        payer = seller,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = seller,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
+        has_one = token_lock,
     )]
-    pub seller_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub lock: Box<Account<'info, LPLock>>,
 
-    /// CHECK: USDC devnet constant
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub token_lock: Box<Account<'info, TokenLock>>,
 
-    /// CHECK: platform wallet (SystemAccount)
-    #[account(mut, address = PLATFORM_WALLET_PUBKEY)]
-    pub platform_wallet: SystemAccount<'info>,
+    #[account(constraint = lock.is_position_holder(&position_ata, holder.key()) @ LaunchError::NotPositionHolder)]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(
-        init_if_needed,
-        payer = seller,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = platform_wallet,
-    )]
-    pub platform_profit_ata: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = token_lock.vault)]
+    pub lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_lock.mint)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
-        payer = seller,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = launch_config,
+        payer = holder,
+        associated_token::mint = lp_mint,
+        associated_token::authority = holder,
     )]
-    pub creator_profit_ata: Box<Account<'info, TokenAccount>>,
+    pub holder_lp_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -1060,266 +7196,384 @@ pub struct Sell<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ClaimProfits<'info> {
+pub struct SetLpFeeConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestLpFees<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub caller: Signer<'info>,
+
+    #[account(constraint = lock.is_position_holder(&position_ata, caller.key()) @ LaunchError::NotPositionHolder)]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    /// CHECK: whitelisted AMM adapter program
+    #[account(
+        executable,
+        address = platform_config.amm_adapter,
+        constraint = platform_config.amm_adapter != Pubkey::default() @ LaunchError::NoAmmAdapter,
+    )]
+    pub amm_adapter: UncheckedAccount<'info>,
 
     #[account(
         mut,
         seeds = [b"launch", mint.key().as_ref()],
         bump = launch_config.bump,
+        constraint = launch_config.phase == LaunchPhase::Graduated @ LaunchError::WrongPhase,
     )]
     pub launch_config: Box<Account<'info, LaunchConfig>>,
 
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + BuyerLedger::INIT_SPACE,
-        seeds = [b"buyer_ledger", mint.key().as_ref(), user.key().as_ref()],
-        bump,
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = launch_config,
+        associated_token::token_program = token_2022_program,
     )]
-    pub buyer_ledger: Box<Account<'info, BuyerLedger>>,
+    pub bonding_curve_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
+        has_one = token_lock,
+        constraint = lock.launch == launch_config.key() @ LaunchError::WrongPhase,
+    )]
+    pub lock: Box<Account<'info, LPLock>>,
 
-    #[account(mut)]
-    pub user_x_ata: Box<Account<'info, TokenAccount>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
+
+    #[account(address = token_lock.vault)]
+    pub lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
-        payer = user,
+        payer = caller,
         associated_token::mint = usdc_mint,
-        associated_token::authority = user,
+        associated_token::authority = token_lock,
     )]
-    pub user_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub fee_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint,
+        associated_token::authority = token_lock,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub fee_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = usdc_vault_auth,
+    )]
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: USDC vault authority PDA
     #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
     pub usdc_vault_auth: UncheckedAccount<'info>,
 
-    /// CHECK: USDC devnet constant
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct Finalize<'info> {
-    #[account(mut)]
-    pub caller: Signer<'info>,
-
+    // holders' share of the token-side fees, paid out by claim_lp_token_fees
     #[account(
-        mut,
-        seeds = [b"launch", mint.key().as_ref()],
-        bump = launch_config.bump,
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint,
+        associated_token::authority = usdc_vault_auth,
+        associated_token::token_program = token_2022_program,
     )]
-    pub launch_config: Box<Account<'info, LaunchConfig>>,
+    pub holders_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub mint: Account<'info, Mint>,
-
-    #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = caller,
+    )]
+    pub owner_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: USDC vault authority PDA
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = mint,
+        associated_token::authority = caller,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub owner_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: creator from config
-    #[account(address = launch_config.creator)]
-    pub creator: UncheckedAccount<'info>,
+    /// CHECK: platform wallet
+    #[account(address = PLATFORM_WALLET_PUBKEY)]
+    pub platform_wallet: UncheckedAccount<'info>,
 
-    /// USDC mint (devnet)
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = platform_wallet,
+    )]
+    pub platform_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = caller,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = creator,
+        associated_token::mint = mint,
+        associated_token::authority = platform_wallet,
+        associated_token::token_program = token_2022_program,
     )]
-    pub creator_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub platform_x_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: USDC devnet constant
+    #[account(address = USDC_DEVNET)]
+    pub usdc_mint: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ReclaimVirtualFunds<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+pub struct ProposeLpMigration<'info> {
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
-        has_one = creator,
-        seeds = [b"launch", mint.key().as_ref()],
-        bump = launch_config.bump,
-        constraint = launch_config.closed || launch_config.migrated,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
     )]
-    pub launch_config: Box<Account<'info, LaunchConfig>>,
+    pub lock: Box<Account<'info, LPLock>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(constraint = lock.is_position_holder(&position_ata, authority.key()) @ LaunchError::NotPositionHolder)]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+}
 
-    #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+#[derive(Accounts)]
+pub struct ApproveLpMigration<'info> {
+    pub authority: Signer<'info>,
 
-    /// CHECK: USDC vault authority PDA
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
+    #[account(
+        has_one = authority,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
 
-    /// USDC mint (devnet)
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
+    )]
+    pub lock: Box<Account<'info, LPLock>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLpMigration<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
 
     #[account(
-        init_if_needed,
-        payer = creator,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = creator,
+        mut,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
     )]
-    pub creator_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub lock: Box<Account<'info, LPLock>>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    pub position_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 }
 
 #[derive(Accounts)]
-pub struct CloseAndMigrateToRaydium<'info> {
+pub struct MigrateLP<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
-        constraint = launch_config.creator == payer.key(),
-        seeds = [b"launch", mint.key().as_ref()],
-        bump = launch_config.bump,
-        constraint = launch_config.closed,
-        close = payer,
+        seeds = [b"lp-lock", lock.amm_id.as_ref()],
+        bump = lock.bump,
+        has_one = token_lock,
+        constraint = lock.migration_allowed @ LaunchError::MigrationNotAllowed,
     )]
-    pub launch_config: Box<Account<'info, LaunchConfig>>,
-
-    pub mint: Account<'info, Mint>,
+    pub lock: Box<Account<'info, LPLock>>,
 
     #[account(mut)]
-    pub bonding_curve_ata: Box<Account<'info, TokenAccount>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
 
-    #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = lock.is_position_holder(&position_ata, authority.key()) @ LaunchError::NotPositionHolder)]
+    pub position_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: USDC vault authority PDA
-    #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
-    pub usdc_vault_auth: UncheckedAccount<'info>,
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
 
-    /// CHECK: AMM ID for Raydium pool
+    /// CHECK: whitelisted AMM adapter program
+    #[account(
+        executable,
+        address = platform_config.amm_adapter,
+        constraint = platform_config.amm_adapter != Pubkey::default() @ LaunchError::NoAmmAdapter,
+    )]
+    pub amm_adapter: UncheckedAccount<'info>,
+
+    /// CHECK: new AMM ID
     #[account(mut)]
-    pub amm_id: UncheckedAccount<'info>,
+    pub new_amm: UncheckedAccount<'info>,
 
-    pub lp_mint: Account<'info, Mint>,
+    pub lp_mint: InterfaceAccount<'info, Mint>,
 
-    #[account(mut)]
-    pub coin_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = token_lock.vault)]
+    pub old_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = lp_mint,
+        associated_token::authority = token_lock,
+    )]
+    pub new_lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CreateLock<'info> {
     #[account(mut)]
-    pub pc_vault: Box<Account<'info, TokenAccount>>,
+    pub owner: Signer<'info>,
 
     #[account(
         init,
-        payer = payer,
-        space = 8 + LPLock::INIT_SPACE,
-        seeds = [b"lp-lock", amm_id.key().as_ref()],
+        payer = owner,
+        space = 8 + TokenLock::INIT_SPACE,
+        seeds = [b"token-lock", owner.key().as_ref(), &id.to_le_bytes()],
         bump,
     )]
-    pub lp_lock: Box<Account<'info, LPLock>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = lp_mint,
-        associated_token::authority = lp_lock_auth,
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
     )]
-    pub lp_lock_vault: Box<Account<'info, TokenAccount>>,
+    pub owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: LP lock authority PDA
-    #[account(seeds = [b"lp-lock", amm_id.key().as_ref()], bump)]
-    pub lp_lock_auth: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = token_lock,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// CHECK: mint authority PDA
-    #[account(seeds = [b"mint-auth", mint.key().as_ref()], bump)]
-    pub mint_auth: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// CHECK: creator from config
-    #[account(address = launch_config.creator)]
-    pub creator: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct DepositLock<'info> {
+    pub owner: Signer<'info>,
 
     #[account(
-        init_if_needed,
-        payer = payer,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = creator,
+        mut,
+        has_one = owner,
+        has_one = vault,
+        seeds = [b"token-lock", owner.key().as_ref(), &token_lock.id.to_le_bytes()],
+        bump = token_lock.bump,
     )]
-    pub creator_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
 
-    /// CHECK: USDC devnet constant
-    #[account(address = USDC_DEVNET)]
-    pub usdc_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = token_lock.mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub owner_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct MigrateLP<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+pub struct ExtendTokenLock<'info> {
+    pub owner: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"lp-lock", lock.amm_id.as_ref()],
-        bump = lock.bump,
-        constraint = lock.migration_allowed @ LaunchError::MigrationNotAllowed,
-        constraint = Clock::get()?.unix_timestamp >= lock.unlock_timestamp @ LaunchError::StillLocked,
-        constraint = lock.authority == authority.key() @ LaunchError::NotYourLedger,
+        has_one = owner,
+        seeds = [b"token-lock", owner.key().as_ref(), &token_lock.id.to_le_bytes()],
+        bump = token_lock.bump,
     )]
-    pub lock: Box<Account<'info, LPLock>>,
+    pub token_lock: Box<Account<'info, TokenLock>>,
+}
 
-    /// CHECK: new AMM ID
+#[derive(Accounts)]
+pub struct ReleaseLock<'info> {
     #[account(mut)]
-    pub new_amm: UncheckedAccount<'info>,
+    pub beneficiary: Signer<'info>,
 
-    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = vault,
+        seeds = [b"token-lock", token_lock.owner.as_ref(), &token_lock.id.to_le_bytes()],
+        bump = token_lock.bump,
+    )]
+    pub token_lock: Box<Account<'info, TokenLock>>,
 
     #[account(mut)]
-    pub old_vault: Box<Account<'info, TokenAccount>>,
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut)]
-    pub new_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = token_lock.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
-        payer = authority,
-        associated_token::mint = lp_mint,
-        associated_token::authority = lp_lock_auth,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
     )]
-    pub new_lp_lock_vault: Box<Account<'info, TokenAccount>>,
-
-    /// CHECK: LP lock authority PDA
-    #[account(seeds = [b"lp-lock", lock.amm_id.as_ref()], bump)]
-    pub lp_lock_auth: UncheckedAccount<'info>,
+    pub beneficiary_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct SetLockBeneficiary<'info> {
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [b"token-lock", token_lock.owner.as_ref(), &token_lock.id.to_le_bytes()],
+        bump = token_lock.bump,
+    )]
+    pub token_lock: Box<Account<'info, TokenLock>>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawCreatorReserve<'info> {
     #[account(mut)]
@@ -1333,10 +7587,13 @@ pub struct WithdrawCreatorReserve<'info> {
     )]
     pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: USDC vault authority PDA
     #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
@@ -1352,7 +7609,7 @@ pub struct WithdrawCreatorReserve<'info> {
         associated_token::mint = usdc_mint,
         associated_token::authority = creator,
     )]
-    pub creator_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub creator_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -1374,7 +7631,10 @@ pub struct WithdrawPlatformRemaining<'info> {
     )]
     pub launch_config: Box<Account<'info, LaunchConfig>>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(seeds = [b"platform"], bump = platform_config.bump)]
+    pub platform_config: Box<Account<'info, PlatformConfig>>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
@@ -1382,10 +7642,10 @@ pub struct WithdrawPlatformRemaining<'info> {
         associated_token::mint = usdc_mint,
         associated_token::authority = platform,
     )]
-    pub platform_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub platform_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    pub usdc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: vault authority PDA
     #[account(seeds = [b"usdc-vault", mint.key().as_ref()], bump)]
@@ -1398,4 +7658,556 @@ pub struct WithdrawPlatformRemaining<'info> {
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_option::COption;
+    use anchor_lang::solana_program::program_pack::Pack;
+
+    // ---- fixtures ----
+
+    // all-zero account, i.e. what a freshly initialized account holds before any instruction
+    fn zeroed<T: AnchorDeserialize + Space>() -> T {
+        T::deserialize(&mut &vec![0u8; T::INIT_SPACE][..]).unwrap()
+    }
+
+    fn error_code<T>(result: Result<T>) -> u32 {
+        match result {
+            Err(Error::AnchorError(e)) => e.error_code_number,
+            _ => panic!("expected an anchor error"),
+        }
+    }
+
+    // writable account with leaked backing storage, as the runtime would hand it to the program
+    fn account_info(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            true,
+            Box::leak(Box::new(1_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )))
+    }
+
+    fn token_account_state(mint: Pubkey, owner: Pubkey, state: AccountState) -> TokenAccountState {
+        TokenAccountState {
+            mint,
+            owner,
+            amount: 1_000,
+            state,
+            ..Default::default()
+        }
+    }
+
+    fn packed(account: TokenAccountState) -> Vec<u8> {
+        let mut data = vec![0u8; TokenAccountState::LEN];
+        TokenAccountState::pack(account, &mut data).unwrap();
+        data
+    }
+
+    fn lp_vault(
+        owner: Pubkey,
+        delegate: Option<Pubkey>,
+        close_authority: Option<Pubkey>,
+    ) -> TokenAccount {
+        let account = TokenAccountState {
+            delegate: delegate.map_or(COption::None, COption::Some),
+            delegated_amount: delegate.map_or(0, |_| 1_000),
+            close_authority: close_authority.map_or(COption::None, COption::Some),
+            ..token_account_state(Pubkey::new_unique(), owner, AccountState::Initialized)
+        };
+        TokenAccount::try_deserialize(&mut &packed(account)[..]).unwrap()
+    }
+
+    fn ledger_info(mint: &Pubkey, buyer: Pubkey) -> &'static AccountInfo<'static> {
+        let (key, bump) = Pubkey::find_program_address(
+            &[b"buyer_ledger", mint.as_ref(), buyer.as_ref()],
+            &crate::ID,
+        );
+        let mut ledger: BuyerLedger = zeroed();
+        ledger.buyer = buyer;
+        ledger.bump = bump;
+        let mut data = Vec::with_capacity(8 + BuyerLedger::INIT_SPACE);
+        ledger.try_serialize(&mut data).unwrap();
+        account_info(key, crate::ID, data)
+    }
+
+    fn read_ledger(ledger_ai: &AccountInfo) -> BuyerLedger {
+        BuyerLedger::try_deserialize(&mut &ledger_ai.try_borrow_data().unwrap()[..]).unwrap()
+    }
+
+    fn order(owner: Pubkey, is_buy: bool, batch_id: u64) -> BatchOrder {
+        BatchOrder {
+            owner,
+            destination: Pubkey::new_unique(),
+            is_buy,
+            amount_in: 700,
+            min_out: 1,
+            batch_id,
+        }
+    }
+
+    fn tranche(delay_secs: i64, bps: u16) -> LpTrancheParams {
+        LpTrancheParams { delay_secs, bps }
+    }
+
+    fn auction_config() -> LaunchConfig {
+        let mut config: LaunchConfig = zeroed();
+        config.virtual_usdc = 10_000_000_000; // 10k USDC
+        config.virtual_token = 1_000_000_000_000; // 1M tokens => 0.01 USDC spot
+        config.auction_min_price = 10_000;
+        config.auction_tick_size = 1_000;
+        config
+    }
+
+    fn lbp_config() -> LaunchConfig {
+        let mut config: LaunchConfig = zeroed();
+        config.curve_start_time = 1_000;
+        config.deadline = 1_000 + LAUNCH_DURATION;
+        config.lbp_start_weight_bps = 9_000;
+        config.lbp_end_weight_bps = 5_000;
+        config
+    }
+
+    fn presale_config() -> LaunchConfig {
+        let mut config: LaunchConfig = zeroed();
+        config.round_tokens_sold = 1_000;
+        config.round_raised = 500;
+        config.round_soft_cap = 500;
+        config.auction_tokens_filled = 2_000;
+        config.lottery_ticket_usdc = 10;
+        config.lottery_entries = 8;
+        config.lottery_max_winners = 5;
+        config.lottery_tokens_per_ticket = 100;
+        config
+    }
+
+    fn lp_token_lock() -> TokenLock {
+        let mut lock: TokenLock = zeroed();
+        lock.deposited = 1_000;
+        lock.released = 250;
+        lock.start_time = 0;
+        lock.cliff_time = 0;
+        lock.end_time = 1_000;
+        lock
+    }
+
+    // ---- batch auction ----
+
+    #[test]
+    fn clear_auction_fills_all_demand_below_the_top_tick_price() {
+        let config = auction_config();
+        let mut demand = [0u64; AUCTION_TICKS];
+        demand[40] = 500_000_000; // 500 USDC at 0.05
+        let c = config.clear_auction(&demand);
+        assert_eq!(
+            (c.marginal_tick, c.filled, c.marginal_fill),
+            (40, 500_000_000, 500_000_000)
+        );
+        assert_eq!(
+            c.tokens_filled,
+            bonding_curve_buy(500_000_000, 10_000_000_000, 1_000_000_000_000, 6)
+        );
+        // uniform price sits between spot and the bid's limit
+        assert!(c.clearing_price > 10_000 && c.clearing_price <= config.auction_tick_price(40));
+    }
+
+    #[test]
+    fn clear_auction_fills_the_marginal_tick_pro_rata() {
+        let config = auction_config();
+        let mut demand = [0u64; AUCTION_TICKS];
+        demand[10] = 1_000_000_000_000; // far more than tick 10 can absorb
+        demand[5] = 1_000_000;
+        let c = config.clear_auction(&demand);
+        // tick 10 (0.02) absorbs up to x + Q = 0.02 * y, i.e. Q = 10k USDC; tick 5 gets nothing
+        assert_eq!(c.marginal_tick, 10);
+        assert_eq!(c.marginal_demand, 1_000_000_000_000);
+        assert_eq!(c.filled, 10_000_000_000);
+        assert_eq!(c.marginal_fill, c.filled);
+        assert_eq!(c.clearing_price, config.auction_tick_price(10));
+    }
+
+    #[test]
+    fn clear_auction_without_bids_fills_nothing() {
+        let c = auction_config().clear_auction(&[0u64; AUCTION_TICKS]);
+        assert_eq!((c.filled, c.tokens_filled, c.clearing_price), (0, 0, 0));
+    }
+
+    // ---- liquidity bootstrapping ----
+
+    #[test]
+    fn lbp_weight_interpolates_and_clamps_to_the_window() {
+        let config = lbp_config();
+        assert!(config.has_lbp());
+        assert_eq!(config.lbp_token_weight(0), 9_000);
+        assert_eq!(config.lbp_token_weight(1_000), 9_000);
+        assert_eq!(config.lbp_token_weight(1_000 + LAUNCH_DURATION / 2), 7_000);
+        assert_eq!(config.lbp_token_weight(1_000 + LAUNCH_DURATION), 5_000);
+        assert_eq!(config.lbp_token_weight(i64::MAX), 5_000);
+    }
+
+    #[test]
+    fn lbp_at_equal_weights_matches_constant_product() {
+        let (x, y) = (10_000_000_000u64, 1_000_000_000_000u64);
+        let usdc_in = 250_000_000;
+        let weighted = lbp_buy(usdc_in, x, y, 5_000);
+        let product = bonding_curve_buy(usdc_in, x, y, 6);
+        assert!(
+            weighted.abs_diff(product) <= product / 1_000_000,
+            "{weighted} vs {product}"
+        );
+        let tokens_in = 1_000_000_000;
+        let weighted = lbp_sell(tokens_in, x, y, 5_000);
+        let product = bonding_curve_sell(tokens_in, x, y, 6);
+        assert!(
+            weighted.abs_diff(product) <= product / 1_000_000,
+            "{weighted} vs {product}"
+        );
+    }
+
+    #[test]
+    fn lbp_heavier_token_weight_prices_tokens_higher() {
+        let (x, y) = (10_000_000_000u64, 1_000_000_000_000u64);
+        assert!(lbp_buy(100_000_000, x, y, 9_000) < lbp_buy(100_000_000, x, y, 5_000));
+        assert!(lbp_sell(1_000_000_000, x, y, 9_000) > lbp_sell(1_000_000_000, x, y, 5_000));
+    }
+
+    // ---- batch settlement ----
+
+    #[test]
+    fn batch_orders_expire_after_max_order_batches() {
+        let o = order(Pubkey::new_unique(), true, 5);
+        assert!(!o.expired(5));
+        assert!(!o.expired(5 + MAX_ORDER_BATCHES - 2));
+        assert!(o.expired(5 + MAX_ORDER_BATCHES - 1));
+        assert!(o.expired(u64::MAX));
+    }
+
+    #[test]
+    fn can_receive_skips_destinations_that_would_abort_the_batch() {
+        let mint = Pubkey::new_unique();
+        let token_2022 = Token2022::id();
+        let holder = Pubkey::new_unique();
+        let account = |mint, state| {
+            account_info(
+                Pubkey::new_unique(),
+                token_2022,
+                packed(token_account_state(mint, holder, state)),
+            )
+        };
+        let open = account(mint, AccountState::Initialized);
+        assert!(can_receive(open, &token_2022, &mint));
+        // closed: handed back to the system program with no data
+        let closed = account_info(Pubkey::new_unique(), System::id(), vec![]);
+        assert!(!can_receive(closed, &token_2022, &mint));
+        assert!(!can_receive(
+            account(mint, AccountState::Frozen),
+            &token_2022,
+            &mint
+        ));
+        assert!(!can_receive(
+            account(Pubkey::new_unique(), AccountState::Initialized),
+            &token_2022,
+            &mint
+        ));
+        // right layout, wrong token program
+        assert!(!can_receive(open, &Token::id(), &mint));
+    }
+
+    #[test]
+    fn undeliverable_orders_are_credited_to_the_owners_ledger() {
+        let mint = Pubkey::new_unique();
+        let trader = Pubkey::new_unique();
+        let ledger_ai = ledger_info(&mint, trader);
+        credit_batch_refund(ledger_ai, &mint, &order(trader, true, 0)).unwrap();
+        credit_batch_refund(ledger_ai, &mint, &order(trader, true, 1)).unwrap();
+        credit_batch_refund(ledger_ai, &mint, &order(trader, false, 1)).unwrap();
+        let ledger = read_ledger(ledger_ai);
+        assert_eq!(
+            (ledger.batch_refund_usdc, ledger.batch_refund_tokens),
+            (1_400, 700)
+        );
+    }
+
+    #[test]
+    fn batch_refunds_only_credit_the_order_owner() {
+        let mint = Pubkey::new_unique();
+        let ledger_ai = ledger_info(&mint, Pubkey::new_unique());
+        let stranger = order(Pubkey::new_unique(), true, 0);
+        assert_eq!(
+            error_code(credit_batch_refund(ledger_ai, &mint, &stranger)),
+            u32::from(LaunchError::NotYourLedger)
+        );
+    }
+
+    #[test]
+    fn batch_fills_count_towards_the_bond_limit() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let ledger_ai = ledger_info(&mint, creator);
+        let mut config: LaunchConfig = zeroed();
+        config.bond_max_sell_bps = 5_000;
+        // a buy filled through the book counts like a curve buy
+        record_batch_fill(ledger_ai, &mint, &order(creator, true, 0), 1_000).unwrap();
+        assert_eq!(read_ledger(ledger_ai).tokens_bought, 1_000);
+        // a sell order that is refunded instead of filled never counts as sold
+        credit_batch_refund(ledger_ai, &mint, &order(creator, false, 0)).unwrap();
+        let ledger = read_ledger(ledger_ai);
+        assert_eq!(ledger.tokens_sold, 0);
+        assert!(!config.bond_violated(&ledger));
+        // filled sells count; 700 of 1_000 bought is past the 50% limit
+        record_batch_fill(ledger_ai, &mint, &order(creator, false, 1), 500).unwrap();
+        assert!(!config.bond_violated(&read_ledger(ledger_ai)));
+        record_batch_fill(ledger_ai, &mint, &order(creator, false, 1), 200).unwrap();
+        assert!(config.bond_violated(&read_ledger(ledger_ai)));
+    }
+
+    #[test]
+    fn match_batch_holds_back_sells_that_would_trip_the_breaker() {
+        let mut config = auction_config();
+        config.breaker_drop_bps = 1_000; // trips 10% below the reference
+        config.breaker_window_slots = 100;
+        let owner = Pubkey::new_unique();
+        let sell = |amount_in| BatchOrder {
+            amount_in,
+            min_out: 0,
+            ..order(owner, false, 0)
+        };
+        let orders = [
+            BatchOrder {
+                amount_in: 100_000_000,
+                ..order(owner, true, 0)
+            },
+            sell(10_000_000_000),  // 1% of the curve inventory
+            sell(200_000_000_000), // 20%: crashes the price on its own
+        ];
+        let mut included = [true; 3];
+        let (num, den) = config.match_batch(&orders, &mut included, 0, 0);
+        assert_eq!(included, [true, true, false]);
+        assert_eq!(num, 10_000_000_000 + 100_000_000);
+        assert_eq!(den, 1_000_000_000_000 + 10_000_000_000);
+
+        // without a breaker the whole book fills
+        config.breaker_drop_bps = 0;
+        let mut included = [true; 3];
+        config.match_batch(&orders, &mut included, 0, 0);
+        assert_eq!(included, [true; 3]);
+    }
+
+    // ---- wind-down ----
+
+    #[test]
+    fn presale_owed_counts_every_unclaimed_allocation() {
+        let mut config = presale_config();
+        // 1_000 round + 2_000 auction + 5 winning tickets * 100
+        assert_eq!(config.presale_owed(), 3_500);
+        config.presale_claimed = 1_200;
+        assert_eq!(config.presale_owed(), 2_300);
+        // an undersubscribed lottery: every ticket wins
+        config.lottery_entries = 3;
+        assert_eq!(config.presale_owed(), 2_100);
+    }
+
+    #[test]
+    fn presale_owed_skips_a_round_below_its_soft_cap() {
+        let mut config = presale_config();
+        config.round_raised = 499;
+        assert_eq!(config.presale_owed(), 2_500);
+    }
+
+    #[test]
+    fn wind_down_fails_every_phase_before_graduation() {
+        for phase in WIND_DOWN_PHASES {
+            assert!(phase.can_transition_to(LaunchPhase::Failed), "{phase:?}");
+        }
+        let mut config: LaunchConfig = zeroed();
+        for phase in [
+            LaunchPhase::Failed,
+            LaunchPhase::Graduated,
+            LaunchPhase::Archived,
+        ] {
+            config.phase = phase;
+            assert_eq!(
+                error_code(config.require_phase(&WIND_DOWN_PHASES)),
+                u32::from(LaunchError::WrongPhase)
+            );
+        }
+    }
+
+    #[test]
+    fn wind_down_voids_an_unsettled_auction() {
+        let mut config = presale_config();
+        config.auction_tokens_filled = 0;
+        config.phase = LaunchPhase::BatchAuction;
+        config.void_opening_sale();
+        // no tick reaches the marginal tick: every bid is refunded in full
+        for tick in 0..AUCTION_TICKS as u8 {
+            assert_eq!(config.auction_fill(tick, 1_000), 0);
+        }
+    }
+
+    #[test]
+    fn wind_down_voids_an_undrawn_lottery() {
+        let mut config = presale_config();
+        config.phase = LaunchPhase::Lottery;
+        config.void_opening_sale();
+        assert!((0..config.lottery_entries).all(|i| !config.lottery_is_winner(i)));
+        // the voided lottery no longer dilutes redemptions
+        assert_eq!(config.presale_owed(), 3_000);
+    }
+
+    // ---- LP lock ----
+
+    #[test]
+    fn migration_carries_the_schedule_over_to_the_new_lp() {
+        let mut lock = lp_token_lock();
+        let old_balance = lock.deposited - lock.released;
+        let releasable = lock.releasable(500);
+        // 750 old LP come back as 300 of the new pool
+        lock.rescale(old_balance, 300);
+        assert_eq!((lock.deposited, lock.released), (400, 100));
+        assert_eq!(lock.deposited - lock.released, 300);
+        assert_eq!(lock.releasable(500), releasable * 2 / 5);
+        assert_eq!(lock.releasable(lock.end_time), 300);
+    }
+
+    #[test]
+    fn migration_into_a_richer_pool_scales_up() {
+        let mut lock = lp_token_lock();
+        lock.rescale(750, 1_500);
+        assert_eq!((lock.deposited, lock.released), (2_000, 500));
+        assert_eq!(lock.releasable(lock.end_time), 1_500);
+    }
+
+    #[test]
+    fn split_lp_fees_gives_the_platform_the_remainder() {
+        let mut platform: PlatformConfig = zeroed();
+        platform.lp_fee_owner_bps = 3_000;
+        platform.lp_fee_holders_bps = 5_000;
+        assert_eq!(
+            platform.split_lp_fees(1_000_000),
+            (300_000, 500_000, 200_000)
+        );
+        // rounding dust goes to the platform, nothing is lost
+        let (owner, holders, rest) = platform.split_lp_fees(999);
+        assert_eq!((owner, holders, rest), (299, 499, 201));
+        assert_eq!(platform.split_lp_fees(0), (0, 0, 0));
+    }
+
+    #[test]
+    fn harvested_token_fees_are_indexed_for_holders() {
+        let mut config: LaunchConfig = zeroed();
+        config.accrue_holders_token_index(500, 10_000);
+        config.accrue_holders_token_index(250, 10_000);
+        assert_eq!(config.holders_reserve_tokens, 750);
+        // a holder of 10% of the circulating supply is owed 10% of the share
+        let owed = 1_000u128 * config.holders_token_index / ACC_SCALE;
+        assert_eq!(owed, 75);
+        // the USDC index is untouched
+        assert_eq!(config.holders_index, 0);
+    }
+
+    #[test]
+    fn harvest_rejects_a_vault_the_adapter_could_still_move() {
+        let lock = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+        let moved = u32::from(LaunchError::PrincipalMoved);
+        assert!(require_lock_custody(&lp_vault(lock, None, None), lock).is_ok());
+        assert_eq!(
+            error_code(require_lock_custody(&lp_vault(outsider, None, None), lock)),
+            moved
+        );
+        assert_eq!(
+            error_code(require_lock_custody(
+                &lp_vault(lock, Some(outsider), None),
+                lock
+            )),
+            moved
+        );
+        assert_eq!(
+            error_code(require_lock_custody(
+                &lp_vault(lock, None, Some(outsider)),
+                lock
+            )),
+            moved
+        );
+    }
+
+    #[test]
+    fn lp_unlock_tranches_default_to_one_full_lock() {
+        let (tranches, count) = lp_unlock_tranches(100, &[]).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            (tranches[0].unlock_time, tranches[0].bps),
+            (100 + LOCK_DURATION, 10_000)
+        );
+    }
+
+    #[test]
+    fn lp_unlock_tranches_release_in_steps() {
+        let year = MIN_LP_TRANCHE_DELAY;
+        let schedule = [
+            tranche(year, 2_500),
+            tranche(2 * year, 2_500),
+            tranche(3 * year, 5_000),
+        ];
+        let (tranches, count) = lp_unlock_tranches(100, &schedule).unwrap();
+        assert_eq!(count, 3);
+        let mut lock: LPLock = zeroed();
+        lock.tranches = tranches;
+        lock.tranche_count = count;
+        assert_eq!(lock.unlocked(1_000, 100 + year - 1), 0);
+        assert_eq!(lock.unlocked(1_000, 100 + year), 250);
+        assert_eq!(lock.unlocked(1_000, 100 + 2 * year), 500);
+        assert_eq!(lock.unlocked(1_000, 100 + 3 * year), 1_000);
+        assert_eq!(lock.final_unlock(), 100 + 3 * year);
+    }
+
+    #[test]
+    fn lp_unlock_tranches_reject_bad_schedules() {
+        let year = MIN_LP_TRANCHE_DELAY;
+        let invalid = u32::from(LaunchError::InvalidUnlockSchedule);
+        for schedule in [
+            vec![tranche(year - 1, 10_000)], // inside the minimum lock
+            vec![tranche(year, 5_000), tranche(year, 5_000)], // not strictly increasing
+            vec![tranche(year, 5_000), tranche(2 * year, 4_999)], // does not add up to 100%
+            vec![tranche(year, 0), tranche(2 * year, 10_000)], // empty tranche
+            vec![tranche(MAX_LOCK_DURATION + 1, 10_000)], // past the maximum lock
+            (1..=MAX_LP_TRANCHES as i64 + 1)
+                .map(|i| tranche(i * year, 1_000))
+                .collect(),
+        ] {
+            assert_eq!(error_code(lp_unlock_tranches(0, &schedule)), invalid);
+        }
+    }
+
+    // ---- ticker registry ----
+
+    #[test]
+    fn normalize_ticker_folds_case_and_padding() {
+        assert_eq!(normalize_ticker("doge"), "DOGE");
+        assert_eq!(normalize_ticker(" Doge "), "DOGE");
+        assert_eq!(normalize_ticker("DOGE"), normalize_ticker("dOgE"));
+        assert_ne!(normalize_ticker("DOGE2"), normalize_ticker("DOGE"));
+    }
+
+    #[test]
+    fn validate_ticker_accepts_only_short_alphanumerics() {
+        let invalid = u32::from(LaunchError::InvalidTicker);
+        for ok in ["A", "DOGE", "PEPE2", "ABCDEFGHIJ"] {
+            assert!(validate_ticker(ok).is_ok(), "{ok}");
+        }
+        for bad in ["", "ABCDEFGHIJK", "DO GE", "DOGE!", "D0GÉ", "$WIF"] {
+            assert_eq!(
+                error_code(validate_ticker(&normalize_ticker(bad))),
+                invalid,
+                "{bad}"
+            );
+        }
+    }
 }
\ No newline at end of file